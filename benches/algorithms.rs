@@ -0,0 +1,271 @@
+use std::fs;
+
+use bencher::{benchmark_group, benchmark_main, Bencher};
+use boyer_moore_magiclen::{Algorithm, BMByte};
+
+#[cfg(windows)]
+const TXT_PATH: &str = r"benches\data\vgilante.txt";
+
+#[cfg(not(windows))]
+const TXT_PATH: &str = r"benches/data/vgilante.txt";
+
+const PATTERN_SHORT: &str = "the";
+const PATTERN_SHORT_RESULT_COUNT: usize = 5034;
+
+const PATTERN_LONG: &str = "Half the screen showed a graphic representation of what the
+scanners had picked up the other side showed an analysis of the
+same data.  The graphics showed an irregular shaped lump fade
+on, stay several frames, then fade out.  At the time the lump
+reminded on screen the analysis showed size about a quarter that
+of the ship they had seen and mass as undetermined.";
+const PATTERN_LONG_RESULT_COUNT: usize = 1;
+
+const NOT_EXIST_PATTERN_SHORT: &str = "xyz";
+const NOT_EXIST_PATTERN_LONG: &str = "xyzabcdefghijklmnopqrstuvwzyz xyzabcdefghijklmnopqrstuvwzyz
+xyzabcdefghijklmnopqrstuvwzyz xyzabcdefghijklmnopqrstuvwzyz
+xyzabcdefghijklmnopqrstuvwzyz xyzabcdefghijklmnopqrstuvwzyz
+xyzabcdefghijklmnopqrstuvwzyz xyzabcdefghijklmnopqrstuvwzyz
+xyzabcdefghijklmnopqrstuvwzyz xyzabcdefghijklmnopqrstuvwzyz";
+
+const PATTERN_MEDIUM: &str = "the other side";
+const PATTERN_MEDIUM_RESULT_COUNT: usize = 5;
+
+const DNA_LEN: usize = 1 << 20;
+const DNA_PATTERN_SHORT: &str = "ACGTA";
+const DNA_PATTERN_MEDIUM: &str = "GATTACAGATTACAGATTACA";
+const DNA_PATTERN_LONG: &str = "GATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACAGATTACA";
+
+fn text() -> String {
+    fs::read_to_string(TXT_PATH).unwrap()
+}
+
+/// Generate a pseudo-random DNA sequence, whose alphabet has only four letters.
+fn dna() -> String {
+    let mut seed = 0x2545_F491u32;
+
+    (0..DNA_LEN)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+
+            b"ACGT"[(seed >> 16) as usize % 4] as char
+        })
+        .collect()
+}
+
+fn bench(
+    bencher: &mut Bencher,
+    algorithm: Algorithm,
+    text: String,
+    pattern: &str,
+    result_count: usize,
+) {
+    let bmb = BMByte::builder(pattern).algorithm(algorithm).build().unwrap();
+
+    bencher.iter(|| {
+        let result = bmb.find_all_in(text.as_str());
+
+        if result_count != usize::MAX {
+            assert_eq!(result_count, result.len());
+        }
+    });
+}
+
+macro_rules! bench_algorithms {
+    ($group:ident, $text:expr, $pattern:expr, $result_count:expr) => {
+        mod $group {
+            use super::*;
+
+            pub fn magiclen(bencher: &mut Bencher) {
+                bench(bencher, Algorithm::MagicLen, $text, $pattern, $result_count);
+            }
+
+            pub fn horspool(bencher: &mut Bencher) {
+                bench(bencher, Algorithm::Horspool, $text, $pattern, $result_count);
+            }
+
+            pub fn sunday(bencher: &mut Bencher) {
+                bench(bencher, Algorithm::Sunday, $text, $pattern, $result_count);
+            }
+
+            pub fn raita(bencher: &mut Bencher) {
+                bench(bencher, Algorithm::Raita, $text, $pattern, $result_count);
+            }
+
+            pub fn tuned_boyer_moore(bencher: &mut Bencher) {
+                bench(bencher, Algorithm::TunedBoyerMoore, $text, $pattern, $result_count);
+            }
+
+            pub fn shift_or(bencher: &mut Bencher) {
+                bench(bencher, Algorithm::ShiftOr, $text, $pattern, $result_count);
+            }
+
+            pub fn bndm(bencher: &mut Bencher) {
+                bench(bencher, Algorithm::Bndm, $text, $pattern, $result_count);
+            }
+        }
+
+        benchmark_group!(
+            $group,
+            $group::magiclen,
+            $group::horspool,
+            $group::sunday,
+            $group::raita,
+            $group::tuned_boyer_moore,
+            $group::shift_or,
+            $group::bndm
+        );
+    };
+}
+
+fn bench_bigram(bencher: &mut Bencher, bigram: bool, text: String, pattern: &str) {
+    let bmb =
+        BMByte::builder(pattern).algorithm(Algorithm::MagicLen).bigram(bigram).build().unwrap();
+
+    bencher.iter(|| bmb.find_all_in(text.as_str()));
+}
+
+mod bigram {
+    use super::*;
+
+    pub fn medium_magiclen(bencher: &mut Bencher) {
+        bench_bigram(bencher, false, text(), PATTERN_MEDIUM);
+    }
+
+    pub fn medium_bigram(bencher: &mut Bencher) {
+        bench_bigram(bencher, true, text(), PATTERN_MEDIUM);
+    }
+
+    pub fn long_magiclen(bencher: &mut Bencher) {
+        bench_bigram(bencher, false, text(), PATTERN_LONG);
+    }
+
+    pub fn long_bigram(bencher: &mut Bencher) {
+        bench_bigram(bencher, true, text(), PATTERN_LONG);
+    }
+
+    pub fn dna_medium_magiclen(bencher: &mut Bencher) {
+        bench_bigram(bencher, false, dna(), DNA_PATTERN_MEDIUM);
+    }
+
+    pub fn dna_medium_bigram(bencher: &mut Bencher) {
+        bench_bigram(bencher, true, dna(), DNA_PATTERN_MEDIUM);
+    }
+
+    pub fn dna_long_magiclen(bencher: &mut Bencher) {
+        bench_bigram(bencher, false, dna(), DNA_PATTERN_LONG);
+    }
+
+    pub fn dna_long_bigram(bencher: &mut Bencher) {
+        bench_bigram(bencher, true, dna(), DNA_PATTERN_LONG);
+    }
+}
+
+benchmark_group!(
+    bigram,
+    bigram::medium_magiclen,
+    bigram::medium_bigram,
+    bigram::long_magiclen,
+    bigram::long_bigram,
+    bigram::dna_medium_magiclen,
+    bigram::dna_medium_bigram,
+    bigram::dna_long_magiclen,
+    bigram::dna_long_bigram
+);
+
+#[cfg(feature = "character")]
+mod character {
+    use boyer_moore_magiclen::{BMCharacter, BMCharacterBuilder};
+
+    use super::*;
+
+    fn bench(
+        bencher: &mut Bencher,
+        builder: fn(Vec<char>) -> BMCharacterBuilder<Vec<char>>,
+        text: String,
+        pattern: &str,
+    ) {
+        let text: Vec<char> = text.chars().collect();
+
+        let bmc = builder(pattern.chars().collect()).build().unwrap();
+
+        bencher.iter(|| bmc.find_all_in(&text));
+    }
+
+    // BNDM is chosen automatically for short patterns unless the good suffix rule is enabled
+    fn bit_parallel(pattern: Vec<char>) -> BMCharacterBuilder<Vec<char>> {
+        BMCharacter::builder(pattern)
+    }
+
+    fn good_suffix(pattern: Vec<char>) -> BMCharacterBuilder<Vec<char>> {
+        BMCharacter::builder(pattern).good_suffix(true)
+    }
+
+    pub fn short_bit_parallel(bencher: &mut Bencher) {
+        bench(bencher, bit_parallel, text(), PATTERN_SHORT);
+    }
+
+    pub fn short_good_suffix(bencher: &mut Bencher) {
+        bench(bencher, good_suffix, text(), PATTERN_SHORT);
+    }
+
+    pub fn medium_bit_parallel(bencher: &mut Bencher) {
+        bench(bencher, bit_parallel, text(), PATTERN_MEDIUM);
+    }
+
+    pub fn medium_good_suffix(bencher: &mut Bencher) {
+        bench(bencher, good_suffix, text(), PATTERN_MEDIUM);
+    }
+
+    pub fn dna_medium_bit_parallel(bencher: &mut Bencher) {
+        bench(bencher, bit_parallel, dna(), DNA_PATTERN_MEDIUM);
+    }
+
+    pub fn dna_medium_good_suffix(bencher: &mut Bencher) {
+        bench(bencher, good_suffix, dna(), DNA_PATTERN_MEDIUM);
+    }
+}
+
+#[cfg(feature = "character")]
+benchmark_group!(
+    character,
+    character::short_bit_parallel,
+    character::short_good_suffix,
+    character::medium_bit_parallel,
+    character::medium_good_suffix,
+    character::dna_medium_bit_parallel,
+    character::dna_medium_good_suffix
+);
+
+// `ShiftOr` and `Bndm` fall back to `MagicLen` for the patterns longer than 64 bytes
+bench_algorithms!(short, text(), PATTERN_SHORT, PATTERN_SHORT_RESULT_COUNT);
+bench_algorithms!(medium, text(), PATTERN_MEDIUM, PATTERN_MEDIUM_RESULT_COUNT);
+bench_algorithms!(long, text(), PATTERN_LONG, PATTERN_LONG_RESULT_COUNT);
+bench_algorithms!(not_exist_short, text(), NOT_EXIST_PATTERN_SHORT, 0);
+bench_algorithms!(not_exist_long, text(), NOT_EXIST_PATTERN_LONG, 0);
+bench_algorithms!(dna_short, dna(), DNA_PATTERN_SHORT, usize::MAX);
+bench_algorithms!(dna_medium, dna(), DNA_PATTERN_MEDIUM, usize::MAX);
+
+#[cfg(feature = "character")]
+benchmark_main!(
+    short,
+    medium,
+    long,
+    not_exist_short,
+    not_exist_long,
+    dna_short,
+    dna_medium,
+    bigram,
+    character
+);
+
+#[cfg(not(feature = "character"))]
+benchmark_main!(
+    short,
+    medium,
+    long,
+    not_exist_short,
+    not_exist_long,
+    dna_short,
+    dna_medium,
+    bigram
+);
@@ -59,6 +59,16 @@ fn short_bmb(bencher: &mut Bencher) {
     });
 }
 
+fn short_bmb_n(bencher: &mut Bencher) {
+    let text = fs::read_to_string(TXT_PATH).unwrap();
+
+    bencher.iter(|| {
+        let result = bmb_n_search::<3, _, _>(text.as_str(), PATTERN_SHORT);
+
+        assert_eq!(PATTERN_SHORT_RESULT_COUNT, result.len());
+    });
+}
+
 #[cfg(feature = "character")]
 fn short_character(bencher: &mut Bencher) {
     let text = fs::read_to_string(TXT_PATH).unwrap();
@@ -74,10 +84,10 @@ fn short_character(bencher: &mut Bencher) {
 }
 
 #[cfg(feature = "character")]
-benchmark_group!(short, short_naive, short_regex, short_bmb, short_character);
+benchmark_group!(short, short_naive, short_regex, short_bmb, short_bmb_n, short_character);
 
 #[cfg(not(feature = "character"))]
-benchmark_group!(short, short_naive, short_regex, short_bmb);
+benchmark_group!(short, short_naive, short_regex, short_bmb, short_bmb_n);
 
 fn long_naive(bencher: &mut Bencher) {
     let text = fs::read_to_string(TXT_PATH).unwrap();
@@ -159,6 +169,16 @@ fn not_exist_short_bmb(bencher: &mut Bencher) {
     });
 }
 
+fn not_exist_short_bmb_n(bencher: &mut Bencher) {
+    let text = fs::read_to_string(TXT_PATH).unwrap();
+
+    bencher.iter(|| {
+        let result = bmb_n_search::<3, _, _>(text.as_str(), NOT_EXIST_PATTERN_SHORT);
+
+        assert_eq!(0, result.len());
+    });
+}
+
 #[cfg(feature = "character")]
 fn not_exist_short_character(bencher: &mut Bencher) {
     let text = fs::read_to_string(TXT_PATH).unwrap();
@@ -179,6 +199,7 @@ benchmark_group!(
     not_exist_short_naive,
     not_exist_short_regex,
     not_exist_short_bmb,
+    not_exist_short_bmb_n,
     not_exist_short_character
 );
 
@@ -187,7 +208,8 @@ benchmark_group!(
     not_exist_short,
     not_exist_short_naive,
     not_exist_short_regex,
-    not_exist_short_bmb
+    not_exist_short_bmb,
+    not_exist_short_bmb_n
 );
 
 fn not_exist_long_naive(bencher: &mut Bencher) {
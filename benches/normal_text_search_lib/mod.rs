@@ -61,6 +61,13 @@ pub fn bmb_search<TT: BMByteSearchable, TP: BMByteSearchable>(text: TT, pattern:
     boyer_moore_magiclen::byte::find(text, pattern, &bad_char_shift_map, 0)
 }
 
+pub fn bmb_n_search<const N: usize, TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+) -> Vec<usize> {
+    BMByteN::<N>::from(pattern).unwrap().find_all_in(text)
+}
+
 #[cfg(feature = "character")]
 pub fn character_search_char<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
     text: TT,
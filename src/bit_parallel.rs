@@ -0,0 +1,125 @@
+//! The bit-parallel algorithms (Shift-Or and BNDM) for patterns which are not longer than `MAX_PATTERN_LEN`. The state of every prefix (or factor) of the pattern is kept in one bit of a `u64`.
+//!
+//! Two kinds of masks are used. In a *forward mask* of `c`, the bit `i` is set if the byte (character) at the index `i` of the pattern is `c`. In a *backward mask* of `c`, the bit `m - 1 - i` is set instead. Searching backward is done by searching the reversed text for the reversed pattern, whose forward masks are the backward masks of the pattern and vice versa.
+
+use alloc::vec::Vec;
+
+/// The maximum length of a pattern which can be searched by the bit-parallel algorithms.
+pub(crate) const MAX_PATTERN_LEN: usize = u64::BITS as usize;
+
+/// The maximum length of a pattern for which a bit-parallel algorithm is chosen when no algorithm is set. Reading every byte (character) beats skipping a few of them for such short patterns, and the worst case of BNDM (which reads up to `m` characters per position) stays small.
+pub(crate) const AUTO_MAX_PATTERN_LEN: usize = 8;
+
+#[inline]
+fn full_mask(m: usize) -> u64 {
+    if m == MAX_PATTERN_LEN {
+        u64::MAX
+    } else {
+        (1 << m) - 1
+    }
+}
+
+/// Find the positions of a pattern whose length is `m` in the text by the Shift-Or algorithm, which reads every byte (character) of the text once. `mask` must return the forward mask of a byte (character). The positions are pushed into `result` until its length reaches `limit` (`0` means no limit). If `full` is `false`, the overlapping occurrences are skipped.
+pub(crate) fn shift_or_find<T>(
+    text_len: usize,
+    text_at: impl Fn(usize) -> T,
+    m: usize,
+    mask: impl Fn(T) -> u64,
+    limit: usize,
+    full: bool,
+    result: &mut Vec<usize>,
+) {
+    let high_bit = 1 << (m - 1);
+
+    // a bit is 0 if the corresponding prefix of the pattern matches the text ending here
+    let mut d = u64::MAX;
+
+    for i in 0..text_len {
+        d = (d << 1) | !mask(text_at(i));
+
+        if d & high_bit == 0 {
+            result.push(i + 1 - m);
+
+            if result.len() == limit {
+                return;
+            }
+
+            if !full {
+                d = u64::MAX;
+            }
+        }
+    }
+}
+
+/// Find the positions of a pattern whose length is `m` in the text by the BNDM (Backward Nondeterministic DAWG Matching) algorithm, which reads the window from its tail and skips bytes (characters) like Boyer-Moore. `mask` must return the backward mask of a byte (character). The positions are pushed into `result` until its length reaches `limit` (`0` means no limit). If `full` is `false`, the overlapping occurrences are skipped.
+pub(crate) fn bndm_find<T>(
+    text_len: usize,
+    text_at: impl Fn(usize) -> T,
+    m: usize,
+    mask: impl Fn(T) -> u64,
+    limit: usize,
+    full: bool,
+    result: &mut Vec<usize>,
+) {
+    if text_len < m {
+        return;
+    }
+
+    let full_mask = full_mask(m);
+    let high_bit = 1 << (m - 1);
+
+    let end_index = text_len - m;
+
+    let mut shift = 0;
+
+    while shift <= end_index {
+        let mut j = m;
+        // the start of the longest suffix of the window which is also a prefix of the pattern
+        let mut last = m;
+
+        let mut d = full_mask;
+
+        loop {
+            d &= mask(text_at(shift + j - 1));
+
+            if d == 0 {
+                break;
+            }
+
+            j -= 1;
+
+            if d & high_bit != 0 {
+                if j > 0 {
+                    last = j;
+                } else {
+                    result.push(shift);
+
+                    if result.len() == limit {
+                        return;
+                    }
+
+                    if !full {
+                        last = m;
+                    }
+
+                    break;
+                }
+            }
+
+            d <<= 1;
+        }
+
+        shift += last;
+    }
+}
+
+/// Create the forward masks and the backward masks of a pattern whose length is `m` (not larger than `MAX_PATTERN_LEN`), and pass every mask to `set` with its byte (character).
+pub(crate) fn for_each_mask<T>(
+    m: usize,
+    at: impl Fn(usize) -> T,
+    mut set: impl FnMut(T, u64, u64),
+) {
+    for i in 0..m {
+        set(at(i), 1 << i, 1 << (m - 1 - i));
+    }
+}
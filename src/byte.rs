@@ -5,6 +5,78 @@ use alloc::{
 };
 use core::{ops::Deref, slice::Iter};
 
+use self::bit_parallel::BMByteBitMasks;
+use crate::{good_suffix, two_way::TwoWay};
+
+#[cfg(feature = "tokio")]
+mod async_stream;
+mod bigram;
+mod bit_parallel;
+#[cfg(feature = "std")]
+mod buf_read;
+mod classic;
+#[cfg(feature = "tokio-util")]
+mod codec;
+#[cfg(any(feature = "flate2", feature = "zstd"))]
+mod compressed;
+#[cfg(feature = "mmap")]
+mod file;
+mod fixed;
+mod incremental;
+#[cfg(feature = "nom")]
+pub mod nom;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod replace_writer;
+#[cfg(feature = "std")]
+mod reverse_stream;
+mod set;
+mod signature;
+#[cfg(feature = "std")]
+mod stream;
+mod tuning;
+
+#[cfg(feature = "tokio")]
+pub use async_stream::AsyncStreamSearcher;
+#[cfg(feature = "std")]
+pub use buf_read::{BMByteBufReadExt, Records};
+#[cfg(feature = "tokio-util")]
+pub use codec::{BMByteCodec, BMByteCodecError};
+#[cfg(any(feature = "flate2", feature = "zstd"))]
+pub use compressed::decompressing_reader;
+pub use fixed::BMByteN;
+pub use incremental::IncrementalMatcher;
+#[cfg(feature = "std")]
+pub use replace_writer::ReplaceWriter;
+#[cfg(feature = "std")]
+pub use reverse_stream::ReverseStreamSearcher;
+pub use set::{BMByteSet, BMByteSetBuilder, MatchKind};
+pub use signature::{FileFormat, Signature, SignatureHit, SignatureScanner};
+#[cfg(feature = "std")]
+pub use stream::StreamSearcher;
+pub use tuning::{BMByteTuning, BMByteTuningReason};
+
+/// The algorithms which can be used by `BMByte` to scan the text. All of them give the same results. The classic ones use the same bad character shift maps, while the bit-parallel ones (`ShiftOr` and `Bndm`) only work for patterns which are not longer than 64 bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Algorithm {
+    /// Boyer-Moore-MagicLen. The shift is the maximum of the Horspool shift and the Sunday shift. This is the default algorithm.
+    #[default]
+    MagicLen,
+    /// Boyer-Moore-Horspool. The shift is decided by the last byte of the window.
+    Horspool,
+    /// Sunday (Quick Search). The shift is decided by the byte right after the window.
+    Sunday,
+    /// Raita. Like Boyer-Moore-Horspool, but the last, the first and the middle bytes of the window are compared before the others.
+    Raita,
+    /// Tuned Boyer-Moore (Hume and Sunday). Like Boyer-Moore-Horspool, but a fast skip loop runs until the last byte of the window matches.
+    TunedBoyerMoore,
+    /// Shift-Or. Every byte of the text is read exactly once and the state of every prefix of the pattern is kept in one bit of a machine word. It is hard to beat for very short patterns or tiny alphabets.
+    ShiftOr,
+    /// BNDM (Backward Nondeterministic DAWG Matching). The window is read from its tail like Boyer-Moore, but the state of every factor of the pattern is kept in one bit of a machine word, so it can skip further on small alphabets (e.g. DNA).
+    Bndm,
+}
+
 #[allow(clippy::len_without_is_empty)]
 pub trait BMByteSearchable {
     fn len(&self) -> usize;
@@ -48,6 +120,23 @@ impl BMByteSearchable for &str {
     }
 }
 
+impl BMByteSearchable for &[u8] {
+    #[inline]
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> u8 {
+        self[index]
+    }
+
+    #[inline]
+    fn iter(&self) -> Iter<'_, u8> {
+        <[u8]>::iter(self)
+    }
+}
+
 impl BMByteSearchable for dyn Deref<Target = [u8]> {
     #[inline]
     fn len(&self) -> usize {
@@ -99,6 +188,26 @@ impl<T: BMByteSearchable> BMByteSearchable for &T {
     }
 }
 
+/// A text read by the scan loops, which only read it by `len` and `value_at`. Unlike `BMByteSearchable`, it has no `iter` method, so the texts whose bytes are transformed when they are read (e.g. `AsciiLowercaseText`) do not have to provide one.
+#[allow(clippy::len_without_is_empty)]
+pub(crate) trait ScanText {
+    fn len(&self) -> usize;
+
+    fn value_at(&self, index: usize) -> u8;
+}
+
+impl<T: BMByteSearchable> ScanText for T {
+    #[inline]
+    fn len(&self) -> usize {
+        BMByteSearchable::len(self)
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> u8 {
+        BMByteSearchable::value_at(self, index)
+    }
+}
+
 pub struct BMByteBadCharShiftMap {
     t: [usize; 256],
 }
@@ -189,12 +298,186 @@ impl BMByteBadCharShiftMapRev {
     }
 }
 
+#[derive(Debug)]
+pub struct BMByteGoodSuffixShiftMap {
+    t: Vec<usize>,
+}
+
+impl Deref for BMByteGoodSuffixShiftMap {
+    type Target = [usize];
+
+    #[inline]
+    fn deref(&self) -> &[usize] {
+        self.t.as_slice()
+    }
+}
+
+#[derive(Debug)]
+pub struct BMByteGoodSuffixShiftMapRev {
+    t: Vec<usize>,
+}
+
+impl Deref for BMByteGoodSuffixShiftMapRev {
+    type Target = [usize];
+
+    #[inline]
+    fn deref(&self) -> &[usize] {
+        self.t.as_slice()
+    }
+}
+
+#[derive(Debug)]
+pub struct BMByteBigramShiftMap {
+    t: Vec<u16>,
+}
+
+impl Deref for BMByteBigramShiftMap {
+    type Target = [u16];
+
+    #[inline]
+    fn deref(&self) -> &[u16] {
+        self.t.as_slice()
+    }
+}
+
+#[derive(Debug)]
+pub struct BMByteBigramShiftMapRev {
+    t: Vec<u16>,
+}
+
+impl Deref for BMByteBigramShiftMapRev {
+    type Target = [u16];
+
+    #[inline]
+    fn deref(&self) -> &[u16] {
+        self.t.as_slice()
+    }
+}
+
+impl BMByteBigramShiftMap {
+    /// Create the shift map keyed on the last two bytes `a` and `b` of the window (at the index `(a << 8) | b`). The shifts are capped at `u16::MAX`. If the pattern is shorter than two bytes, `None` is returned.
+    pub fn create_bigram_shift_map<T: BMByteSearchable>(
+        pattern: T,
+    ) -> Option<BMByteBigramShiftMap> {
+        let pattern_len = pattern.len();
+
+        if pattern_len < 2 {
+            return None;
+        }
+
+        let pattern_len_dec = pattern_len - 1;
+
+        let mut bigram_shift_map = vec![pattern_len_dec.min(u16::MAX as usize) as u16; 1 << 16];
+
+        for i in 1..pattern_len_dec {
+            bigram_shift_map[bigram::bigram_index(pattern.value_at(i - 1), pattern.value_at(i))] =
+                (pattern_len_dec - i).min(u16::MAX as usize) as u16;
+        }
+
+        Some(BMByteBigramShiftMap {
+            t: bigram_shift_map
+        })
+    }
+}
+
+impl BMByteBigramShiftMapRev {
+    /// Create the shift map keyed on the first two bytes `a` and `b` of the window (at the index `(a << 8) | b`). The shifts are capped at `u16::MAX`. If the pattern is shorter than two bytes, `None` is returned.
+    pub fn create_bigram_shift_map<T: BMByteSearchable>(
+        pattern: T,
+    ) -> Option<BMByteBigramShiftMapRev> {
+        let pattern_len = pattern.len();
+
+        if pattern_len < 2 {
+            return None;
+        }
+
+        let pattern_len_dec = pattern_len - 1;
+
+        let mut bigram_shift_map = vec![pattern_len_dec.min(u16::MAX as usize) as u16; 1 << 16];
+
+        for i in (1..pattern_len_dec).rev() {
+            bigram_shift_map[bigram::bigram_index(pattern.value_at(i), pattern.value_at(i + 1))] =
+                i.min(u16::MAX as usize) as u16;
+        }
+
+        Some(BMByteBigramShiftMapRev {
+            t: bigram_shift_map
+        })
+    }
+}
+
+impl BMByteGoodSuffixShiftMap {
+    pub fn create_good_suffix_shift_map<T: BMByteSearchable>(
+        pattern: T,
+    ) -> Option<BMByteGoodSuffixShiftMap> {
+        let pattern_len = pattern.len();
+
+        if pattern_len == 0 {
+            return None;
+        }
+
+        Some(BMByteGoodSuffixShiftMap {
+            t: good_suffix::create_good_suffix_shift_table(pattern_len, |i| pattern.value_at(i)),
+        })
+    }
+}
+
+impl BMByteGoodSuffixShiftMapRev {
+    pub fn create_good_suffix_shift_map<T: BMByteSearchable>(
+        pattern: T,
+    ) -> Option<BMByteGoodSuffixShiftMapRev> {
+        let pattern_len = pattern.len();
+
+        if pattern_len == 0 {
+            return None;
+        }
+
+        Some(BMByteGoodSuffixShiftMapRev {
+            t: good_suffix::create_good_suffix_shift_table_rev(pattern_len, |i| {
+                pattern.value_at(i)
+            }),
+        })
+    }
+}
+
 /// Using Boyer-Moore-MagicLen to search byte sub-sequences in any byte sequence, including self-synchronizing string encoding data such as UTF-8.
 #[derive(Debug)]
 pub struct BMByte {
-    bad_char_shift_map:     BMByteBadCharShiftMap,
-    bad_char_shift_map_rev: BMByteBadCharShiftMapRev,
-    pattern:                Vec<u8>,
+    /// The shift maps are not built if `bit_masks` is used.
+    bad_char_shift_map:        Option<BMByteBadCharShiftMap>,
+    bad_char_shift_map_rev:    Option<BMByteBadCharShiftMapRev>,
+    good_suffix_shift_map:     Option<BMByteGoodSuffixShiftMap>,
+    good_suffix_shift_map_rev: Option<BMByteGoodSuffixShiftMapRev>,
+    bigram_shift_map:          Option<BMByteBigramShiftMap>,
+    bigram_shift_map_rev:      Option<BMByteBigramShiftMapRev>,
+    two_way:                   Option<TwoWay>,
+    two_way_rev:               Option<TwoWay>,
+    bit_masks:                 Option<BMByteBitMasks>,
+    period:                    usize,
+    algorithm:                 Algorithm,
+    tuning:                    Option<BMByteTuning>,
+    ignore_ascii_case:         bool,
+    /// The pattern, in lowercase if `ignore_ascii_case` is `true`. The shift maps are built over it and the text is compared with it.
+    pattern:                   Vec<u8>,
+    /// The pattern as it was given, if it is not the same as `pattern`.
+    original_pattern:          Option<Vec<u8>>,
+}
+
+/// A text whose ASCII letters are read in lowercase, for searching case-insensitively.
+struct AsciiLowercaseText<T: ScanText> {
+    text: T,
+}
+
+impl<T: ScanText> ScanText for AsciiLowercaseText<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> u8 {
+        self.text.value_at(index).to_ascii_lowercase()
+    }
 }
 
 impl BMByte {
@@ -205,151 +488,1273 @@ impl BMByte {
     ///
     /// let bmb = BMByte::from("oocoo").unwrap();
     /// ```
+    #[inline]
     pub fn from<T: BMByteSearchable>(pattern: T) -> Option<BMByte> {
-        let bad_char_shift_map = BMByteBadCharShiftMap::create_bad_char_shift_map(&pattern)?;
-        let bad_char_shift_map_rev = BMByteBadCharShiftMapRev::create_bad_char_shift_map(&pattern)?;
-
-        Some(BMByte {
-            bad_char_shift_map,
-            bad_char_shift_map_rev,
-            pattern: pattern.iter().copied().collect(),
-        })
+        BMByte::builder(pattern).build()
     }
-}
 
-impl BMByte {
-    /// Find and return the positions of all matched sub-sequences in any text (the haystack).
+    /// Create a `BMByte` instance from a pattern (the needle). If `rev` is `false`, the bad character shift map for searching backward will not be built and every `rfind_xxx` method will have to build a temporary one on each call (see `BMByteBuilder::rev`), so only do that if the instance is mainly used for searching forward.
     ///
     /// ```
     /// use boyer_moore_magiclen::BMByte;
     ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
+    /// let bmb = BMByte::from_with_rev("oocoo", false).unwrap();
     ///
-    /// assert_eq!(vec![1, 4, 7], bmb.find_full_all_in("coocoocoocoo"));
+    /// assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
+    /// assert_eq!(vec![7, 1], bmb.rfind_all_in("coocoocoocoo"));
     /// ```
-    pub fn find_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
-        find_full(text, &self.pattern, &self.bad_char_shift_map, 0)
+    #[inline]
+    pub fn from_with_rev<T: BMByteSearchable>(pattern: T, rev: bool) -> Option<BMByte> {
+        BMByte::builder(pattern).rev(rev).build()
     }
 
-    /// Find and return the positions of matched sub-sequences in any text (the haystack). If the `limit` is set to `0`, all sub-sequences will be found.
+    /// Create a `BMByteBuilder` instance to build a `BMByte` instance with options.
     ///
     /// ```
     /// use boyer_moore_magiclen::BMByte;
     ///
+    /// let bmb =
+    ///     BMByte::builder("oocoo").rev(false).good_suffix(true).build().unwrap();
+    /// ```
+    #[inline]
+    pub fn builder<T: BMByteSearchable>(pattern: T) -> BMByteBuilder<T> {
+        BMByteBuilder {
+            pattern,
+            rev: true,
+            good_suffix: false,
+            bigram: false,
+            linear: false,
+            ignore_ascii_case: false,
+            algorithm: None,
+        }
+    }
+
+    /// Get the algorithm used to scan the text.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{Algorithm, BMByte};
+    ///
     /// let bmb = BMByte::from("oocoo").unwrap();
     ///
-    /// assert_eq!(vec![1, 4], bmb.find_full_in("coocoocoocoo", 2));
+    /// assert_eq!(Algorithm::ShiftOr, bmb.algorithm());
+    ///
+    /// let bmb = BMByte::from("oocoo oocoo oocoo").unwrap();
+    ///
+    /// assert_eq!(Algorithm::MagicLen, bmb.algorithm());
     /// ```
-    pub fn find_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        find_full(text, &self.pattern, &self.bad_char_shift_map, limit)
+    #[inline]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
     }
-}
 
-impl BMByte {
-    /// Find and return the positions of all matched sub-sequences in any text (the haystack) from its tail to its head.
+    /// Whether the case of ASCII letters is ignored.
     ///
     /// ```
     /// use boyer_moore_magiclen::BMByte;
     ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
+    /// let bmb = BMByte::builder("Host:").ignore_ascii_case(true).build().unwrap();
     ///
-    /// assert_eq!(vec![7, 4, 1], bmb.rfind_full_all_in("coocoocoocoo"));
+    /// assert!(bmb.ignore_ascii_case());
     /// ```
-    pub fn rfind_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
-        rfind_full(text, &self.pattern, &self.bad_char_shift_map_rev, 0)
+    #[inline]
+    pub fn ignore_ascii_case(&self) -> bool {
+        self.ignore_ascii_case
     }
 
-    /// Find and return the positions of matched sub-sequences in any text (the haystack) from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    /// Get the pattern as it was given, even if the case of ASCII letters is ignored.
     ///
     /// ```
     /// use boyer_moore_magiclen::BMByte;
     ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
+    /// let bmb = BMByte::builder("Host").ignore_ascii_case(true).build().unwrap();
     ///
-    /// assert_eq!(vec![7, 4], bmb.rfind_full_in("coocoocoocoo", 2));
+    /// assert_eq!(b"Host", bmb.pattern());
     /// ```
-    pub fn rfind_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        rfind_full(text, &self.pattern, &self.bad_char_shift_map_rev, limit)
+    #[inline]
+    pub fn pattern(&self) -> &[u8] {
+        self.original_pattern.as_deref().unwrap_or(&self.pattern)
     }
-}
-
-pub fn find_full<TT: BMByteSearchable, TP: BMByteSearchable>(
-    text: TT,
-    pattern: TP,
-    bad_char_shift_map: &BMByteBadCharShiftMap,
-    limit: usize,
-) -> Vec<usize> {
-    let text_len = text.len();
-    let pattern_len = pattern.len();
 
-    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
-        return vec![];
+    /// Fold a byte of the text in the same way as the pattern was folded.
+    #[inline]
+    pub(crate) fn fold(&self, c: u8) -> u8 {
+        if self.ignore_ascii_case {
+            c.to_ascii_lowercase()
+        } else {
+            c
+        }
     }
 
-    let pattern_len_dec = pattern_len - 1;
+    #[inline]
+    fn find_forward<T: ScanText>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        if self.ignore_ascii_case {
+            self.scan_forward(
+                AsciiLowercaseText {
+                    text,
+                },
+                limit,
+                full,
+            )
+        } else {
+            self.scan_forward(text, limit, full)
+        }
+    }
 
-    let last_pattern_char = pattern.value_at(pattern_len_dec);
+    #[inline]
+    fn scan_forward<T: ScanText>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        if let Some(bit_masks) = self.bit_masks.as_ref() {
+            return bit_parallel::find(
+                self.algorithm,
+                text,
+                self.pattern.len(),
+                bit_masks,
+                limit,
+                full,
+            );
+        }
 
-    let mut shift = 0;
+        let bad_char_shift_map = self.bad_char_shift_map.as_ref().unwrap();
+
+        if self.algorithm != Algorithm::MagicLen {
+            return classic::find(
+                self.algorithm,
+                text,
+                &self.pattern,
+                bad_char_shift_map,
+                self.two_way.as_ref(),
+                limit,
+                full,
+            );
+        }
 
-    let end_index = text_len - pattern_len;
+        if let Some(bigram_shift_map) = self.bigram_shift_map.as_ref() {
+            return bigram::find(
+                text,
+                &self.pattern,
+                bad_char_shift_map,
+                bigram_shift_map,
+                self.good_suffix_shift_map.as_ref(),
+                self.two_way.as_ref(),
+                limit,
+                full,
+            );
+        }
 
-    let mut result = vec![];
+        if let Some(two_way) = self.two_way.as_ref() {
+            return find_linear(
+                text,
+                &self.pattern,
+                bad_char_shift_map,
+                self.good_suffix_shift_map.as_ref(),
+                two_way,
+                limit,
+                full,
+            );
+        }
 
-    'outer: loop {
-        for (i, pc) in pattern.iter().copied().enumerate().rev() {
-            if text.value_at(shift + i) != pc {
-                let p = shift + pattern_len;
-                if p == text_len {
-                    break 'outer;
+        match self.good_suffix_shift_map.as_ref() {
+            Some(good_suffix_shift_map) => scan_find_with_good_suffix(
+                text,
+                &self.pattern,
+                bad_char_shift_map,
+                good_suffix_shift_map,
+                limit,
+                full,
+            ),
+            None => {
+                if full {
+                    scan_find_full_with_period(
+                        text,
+                        &self.pattern,
+                        bad_char_shift_map,
+                        self.period,
+                        limit,
+                    )
+                } else {
+                    scan_find(text, &self.pattern, bad_char_shift_map, limit)
                 }
-                shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize].max({
-                    let c = text.value_at(p);
+            },
+        }
+    }
 
-                    if c == last_pattern_char {
-                        1
-                    } else {
-                        bad_char_shift_map[c as usize] + 1
-                    }
+    #[inline]
+    fn find_backward<T: ScanText>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        if self.ignore_ascii_case {
+            self.scan_backward(
+                AsciiLowercaseText {
+                    text,
+                },
+                limit,
+                full,
+            )
+        } else {
+            self.scan_backward(text, limit, full)
+        }
+    }
+
+    fn scan_backward<T: ScanText>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        if let Some(bit_masks) = self.bit_masks.as_ref() {
+            return bit_parallel::rfind(
+                self.algorithm,
+                text,
+                self.pattern.len(),
+                bit_masks,
+                limit,
+                full,
+            );
+        }
+
+        match self.bad_char_shift_map_rev.as_ref() {
+            Some(bad_char_shift_map_rev) => self.find_backward_with(
+                text,
+                limit,
+                full,
+                bad_char_shift_map_rev,
+                self.good_suffix_shift_map_rev.as_ref(),
+                self.bigram_shift_map_rev.as_ref(),
+                self.two_way_rev.as_ref(),
+            ),
+            None => {
+                let bad_char_shift_map_rev =
+                    BMByteBadCharShiftMapRev::create_bad_char_shift_map(&self.pattern).unwrap();
+                let good_suffix_shift_map_rev = self.good_suffix_shift_map.as_ref().map(|_| {
+                    BMByteGoodSuffixShiftMapRev::create_good_suffix_shift_map(&self.pattern)
+                        .unwrap()
                 });
-                if shift > end_index {
-                    break 'outer;
-                }
-                continue 'outer;
-            }
+                let bigram_shift_map_rev = self.bigram_shift_map.as_ref().map(|_| {
+                    BMByteBigramShiftMapRev::create_bigram_shift_map(&self.pattern).unwrap()
+                });
+                let two_way_rev = self.two_way.as_ref().map(|_| create_two_way_rev(&self.pattern));
+
+                self.find_backward_with(
+                    text,
+                    limit,
+                    full,
+                    &bad_char_shift_map_rev,
+                    good_suffix_shift_map_rev.as_ref(),
+                    bigram_shift_map_rev.as_ref(),
+                    two_way_rev.as_ref(),
+                )
+            },
         }
-        result.push(shift);
+    }
 
-        if shift == end_index {
-            break;
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    fn find_backward_with<T: ScanText>(
+        &self,
+        text: T,
+        limit: usize,
+        full: bool,
+        bad_char_shift_map_rev: &BMByteBadCharShiftMapRev,
+        good_suffix_shift_map_rev: Option<&BMByteGoodSuffixShiftMapRev>,
+        bigram_shift_map_rev: Option<&BMByteBigramShiftMapRev>,
+        two_way_rev: Option<&TwoWay>,
+    ) -> Vec<usize> {
+        if self.algorithm != Algorithm::MagicLen {
+            return classic::rfind(
+                self.algorithm,
+                text,
+                &self.pattern,
+                bad_char_shift_map_rev,
+                two_way_rev,
+                limit,
+                full,
+            );
         }
 
-        if result.len() == limit {
-            break;
+        if let Some(bigram_shift_map_rev) = bigram_shift_map_rev {
+            return bigram::rfind(
+                text,
+                &self.pattern,
+                bad_char_shift_map_rev,
+                bigram_shift_map_rev,
+                good_suffix_shift_map_rev,
+                two_way_rev,
+                limit,
+                full,
+            );
         }
 
-        shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize].max({
-            let c = text.value_at(shift + pattern_len);
+        if let Some(two_way_rev) = two_way_rev {
+            return rfind_linear(
+                text,
+                &self.pattern,
+                bad_char_shift_map_rev,
+                good_suffix_shift_map_rev,
+                two_way_rev,
+                limit,
+                full,
+            );
+        }
 
-            if c == last_pattern_char {
-                1
-            } else {
-                bad_char_shift_map[c as usize] + 1
-            }
-        });
-        if shift > end_index {
-            break;
+        match good_suffix_shift_map_rev {
+            Some(good_suffix_shift_map_rev) => scan_rfind_with_good_suffix(
+                text,
+                &self.pattern,
+                bad_char_shift_map_rev,
+                good_suffix_shift_map_rev,
+                limit,
+                full,
+            ),
+            None => {
+                if full {
+                    scan_rfind_full_with_period(
+                        text,
+                        &self.pattern,
+                        bad_char_shift_map_rev,
+                        self.period,
+                        limit,
+                    )
+                } else {
+                    scan_rfind(text, &self.pattern, bad_char_shift_map_rev, limit)
+                }
+            },
         }
     }
+}
 
-    result
+/// A builder to create a `BMByte` instance with options.
+#[derive(Debug, Clone)]
+pub struct BMByteBuilder<T: BMByteSearchable> {
+    pattern:           T,
+    rev:               bool,
+    good_suffix:       bool,
+    bigram:            bool,
+    linear:            bool,
+    ignore_ascii_case: bool,
+    algorithm:         Option<Algorithm>,
 }
 
-pub fn rfind_full<TT: BMByteSearchable, TP: BMByteSearchable>(
-    text: TT,
-    pattern: TP,
-    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+impl<T: BMByteSearchable> BMByteBuilder<T> {
+    /// Whether to build the shift maps for searching backward. If it is set to `false`, every `rfind_xxx` method will have to build temporary ones on each call, which costs an allocation and a pass over the pattern and the 256 entries of the bad character shift map (plus the 65536 entries of the bigram shift map if `bigram` is enabled) before every search. That is only negligible when the texts are much longer than the pattern, so leave it `true` if the instance searches backward in short texts. The default value is `true`.
+    #[inline]
+    pub fn rev(mut self, rev: bool) -> Self {
+        self.rev = rev;
+
+        self
+    }
+
+    /// Whether to also use the good suffix rule of the Boyer-Moore algorithm. The shift will be the maximum of the shift by the bad character rule and the shift by the good suffix rule, which is usually larger for long and repetitive patterns. It only works with `Algorithm::MagicLen`. The default value is `false`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::builder("oocoo").good_suffix(true).build().unwrap();
+    ///
+    /// assert_eq!(vec![1, 4, 7], bmb.find_full_all_in("coocoocoocoo"));
+    /// assert_eq!(vec![7, 1], bmb.rfind_all_in("coocoocoocoo"));
+    /// ```
+    #[inline]
+    pub fn good_suffix(mut self, good_suffix: bool) -> Self {
+        self.good_suffix = good_suffix;
+
+        self
+    }
+
+    /// Whether to use a bigram shift map (65536 entries keyed on the last two bytes of the window, Wu-Manber style) instead of the single-byte bad character shift map for the Horspool half of the shift. It gives much larger shifts for long patterns over small alphabets (e.g. DNA, hex or base64 text), so it only works with `Algorithm::MagicLen` and for patterns which are at least 16 bytes long. If the algorithm is not set, `Algorithm::MagicLen` is chosen for such patterns. The default value is `false`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb =
+    ///     BMByte::builder("GATTACAGATTACAGATTACA").bigram(true).build().unwrap();
+    ///
+    /// assert_eq!(vec![3], bmb.find_all_in("ACGGATTACAGATTACAGATTACAT"));
+    /// ```
+    #[inline]
+    pub fn bigram(mut self, bigram: bool) -> Self {
+        self.bigram = bigram;
+
+        self
+    }
+
+    /// Whether to guarantee the linear worst-case time complexity. If the number of byte comparisons during a search exceeds twice the length of the text, the rest of the text is searched by the Two-Way algorithm instead, so adversarial needles and haystacks (e.g. `"baaaa"` in `"aaaaaaaa"`) cannot cause the quadratic behavior. `Algorithm::Bndm` is replaced by `Algorithm::ShiftOr`, which reads every byte once. This is recommended when the needles or the haystacks are untrusted. The default value is `false`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::builder("baaaa").linear(true).build().unwrap();
+    ///
+    /// assert_eq!(vec![8], bmb.find_all_in("aaaaaaaabaaaa"));
+    /// ```
+    #[inline]
+    pub fn linear(mut self, linear: bool) -> Self {
+        self.linear = linear;
+
+        self
+    }
+
+    /// Whether to ignore the case of ASCII letters. The shift maps are built over the pattern in lowercase, and the bytes of the text are compared in lowercase, for every algorithm and every `find_xxx` and `rfind_xxx` method. The bytes which are not ASCII letters are still compared exactly. The default value is `false`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::builder("content-length:")
+    ///     .ignore_ascii_case(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(17),
+    ///     bmb.find_first_in("Host: localhost\r\nContent-Length: 0\r\n")
+    /// );
+    /// ```
+    #[inline]
+    pub fn ignore_ascii_case(mut self, ignore_ascii_case: bool) -> Self {
+        self.ignore_ascii_case = ignore_ascii_case;
+
+        self
+    }
+
+    /// Set the algorithm used to scan the text. If it is not set, `Algorithm::ShiftOr` is chosen for patterns which are not longer than 8 bytes, and `Algorithm::MagicLen` for the others or when the `good_suffix`, `bigram` or `linear` option is enabled. `Algorithm::Bndm` is never chosen automatically. If `Algorithm::ShiftOr` or `Algorithm::Bndm` is set but the pattern is longer than 64 bytes, `Algorithm::MagicLen` is used instead. If `Algorithm::Bndm` is set with the `linear` option, `Algorithm::ShiftOr` is used instead.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{Algorithm, BMByte};
+    ///
+    /// let bmb = BMByte::builder("oocoo")
+    ///     .algorithm(Algorithm::Horspool)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(vec![1, 4, 7], bmb.find_full_all_in("coocoocoocoo"));
+    /// ```
+    #[inline]
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = Some(algorithm);
+
+        self
+    }
+
+    /// Create a `BMByte` instance. If the pattern is empty, `None` is returned.
+    pub fn build(self) -> Option<BMByte> {
+        let mut pattern: Vec<u8> = self.pattern.iter().copied().collect();
+        let mut original_pattern = None;
+
+        if self.ignore_ascii_case && pattern.iter().any(u8::is_ascii_uppercase) {
+            original_pattern = Some(pattern.clone());
+
+            pattern.make_ascii_lowercase();
+        }
+
+        let fits_in_word = pattern.len() <= crate::bit_parallel::MAX_PATTERN_LEN;
+        let bigram = self.bigram && pattern.len() >= BIGRAM_MIN_PATTERN_LEN;
+
+        let algorithm = match self.algorithm {
+            Some(Algorithm::ShiftOr | Algorithm::Bndm) if !fits_in_word => Algorithm::MagicLen,
+            // BNDM is quadratic in the worst case, while Shift-Or reads every byte once
+            Some(Algorithm::Bndm) if self.linear => Algorithm::ShiftOr,
+            Some(algorithm) => algorithm,
+            None if pattern.len() <= crate::bit_parallel::AUTO_MAX_PATTERN_LEN
+                && !self.good_suffix
+                && !bigram
+                && !self.linear =>
+            {
+                Algorithm::ShiftOr
+            },
+            None => Algorithm::MagicLen,
+        };
+
+        let bit_masks = match algorithm {
+            Algorithm::ShiftOr | Algorithm::Bndm => {
+                Some(BMByteBitMasks::create_bit_masks(&pattern)?)
+            },
+            _ => None,
+        };
+
+        let (bad_char_shift_map, bad_char_shift_map_rev) = if bit_masks.is_some() {
+            (None, None)
+        } else {
+            (
+                Some(BMByteBadCharShiftMap::create_bad_char_shift_map(&pattern)?),
+                if self.rev {
+                    Some(BMByteBadCharShiftMapRev::create_bad_char_shift_map(&pattern)?)
+                } else {
+                    None
+                },
+            )
+        };
+
+        let (good_suffix_shift_map, good_suffix_shift_map_rev) =
+            if self.good_suffix && algorithm == Algorithm::MagicLen {
+                (
+                    Some(BMByteGoodSuffixShiftMap::create_good_suffix_shift_map(&pattern)?),
+                    if self.rev {
+                        Some(BMByteGoodSuffixShiftMapRev::create_good_suffix_shift_map(&pattern)?)
+                    } else {
+                        None
+                    },
+                )
+            } else {
+                (None, None)
+            };
+
+        let (bigram_shift_map, bigram_shift_map_rev) = if bigram && algorithm == Algorithm::MagicLen
+        {
+            (
+                Some(BMByteBigramShiftMap::create_bigram_shift_map(&pattern)?),
+                if self.rev {
+                    Some(BMByteBigramShiftMapRev::create_bigram_shift_map(&pattern)?)
+                } else {
+                    None
+                },
+            )
+        } else {
+            (None, None)
+        };
+
+        let (two_way, two_way_rev) = if self.linear && bit_masks.is_none() {
+            (
+                Some(TwoWay::new(pattern.len(), |i| pattern[i])),
+                if self.rev { Some(create_two_way_rev(&pattern)) } else { None },
+            )
+        } else {
+            (None, None)
+        };
+
+        Some(BMByte {
+            bad_char_shift_map,
+            bad_char_shift_map_rev,
+            good_suffix_shift_map,
+            good_suffix_shift_map_rev,
+            bigram_shift_map,
+            bigram_shift_map_rev,
+            two_way,
+            two_way_rev,
+            bit_masks,
+            period: good_suffix::smallest_period(pattern.len(), |i| pattern[i]),
+            algorithm,
+            tuning: None,
+            ignore_ascii_case: self.ignore_ascii_case,
+            pattern,
+            original_pattern,
+        })
+    }
+}
+
+/// Using Boyer-Moore-MagicLen to search byte sub-sequences in any byte sequence, but only from the head to the tail. Only the bad character shift map for searching forward is built.
+#[derive(Debug)]
+pub struct BMByteForward {
+    bad_char_shift_map: BMByteBadCharShiftMap,
+    period:             usize,
+    pattern:            Vec<u8>,
+}
+
+impl BMByteForward {
+    /// Create a `BMByteForward` instance from a pattern (the needle).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteForward;
+    ///
+    /// let bmb = BMByteForward::from("oocoo").unwrap();
+    /// ```
+    pub fn from<T: BMByteSearchable>(pattern: T) -> Option<BMByteForward> {
+        let bad_char_shift_map = BMByteBadCharShiftMap::create_bad_char_shift_map(&pattern)?;
+
+        Some(BMByteForward {
+            bad_char_shift_map,
+            period: good_suffix::smallest_period(pattern.len(), |i| pattern.value_at(i)),
+            pattern: pattern.iter().copied().collect(),
+        })
+    }
+}
+
+impl BMByteForward {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteForward;
+    ///
+    /// let bmb = BMByteForward::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 4, 7], bmb.find_full_all_in("coocoocoocoo"));
+    /// ```
+    pub fn find_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        find_full_with_period(text, &self.pattern, &self.bad_char_shift_map, self.period, 0)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack). If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteForward;
+    ///
+    /// let bmb = BMByteForward::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 4], bmb.find_full_in("coocoocoocoo", 2));
+    /// ```
+    pub fn find_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        find_full_with_period(text, &self.pattern, &self.bad_char_shift_map, self.period, limit)
+    }
+
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteForward;
+    ///
+    /// let bmb = BMByteForward::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
+    /// ```
+    pub fn find_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        find(text, &self.pattern, &self.bad_char_shift_map, 0)
+    }
+
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteForward;
+    ///
+    /// let bmb = BMByteForward::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(Some(1), bmb.find_first_in("coocoocoocoo"));
+    /// ```
+    pub fn find_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
+        find(text, &self.pattern, &self.bad_char_shift_map, 1).first().copied()
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteForward;
+    ///
+    /// let bmb = BMByteForward::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1], bmb.find_in("coocoocoocoo", 1));
+    /// ```
+    pub fn find_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        find(text, &self.pattern, &self.bad_char_shift_map, limit)
+    }
+}
+
+/// Using Boyer-Moore-MagicLen to search byte sub-sequences in any byte sequence, but only from the tail to the head. Only the bad character shift map for searching backward is built.
+#[derive(Debug)]
+pub struct BMByteReverse {
+    bad_char_shift_map_rev: BMByteBadCharShiftMapRev,
+    period:                 usize,
+    pattern:                Vec<u8>,
+}
+
+impl BMByteReverse {
+    /// Create a `BMByteReverse` instance from a pattern (the needle).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteReverse;
+    ///
+    /// let bmb = BMByteReverse::from("oocoo").unwrap();
+    /// ```
+    pub fn from<T: BMByteSearchable>(pattern: T) -> Option<BMByteReverse> {
+        let bad_char_shift_map_rev = BMByteBadCharShiftMapRev::create_bad_char_shift_map(&pattern)?;
+
+        Some(BMByteReverse {
+            bad_char_shift_map_rev,
+            period: good_suffix::smallest_period(pattern.len(), |i| pattern.value_at(i)),
+            pattern: pattern.iter().copied().collect(),
+        })
+    }
+}
+
+impl BMByteReverse {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteReverse;
+    ///
+    /// let bmb = BMByteReverse::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 4, 1], bmb.rfind_full_all_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        rfind_full_with_period(text, &self.pattern, &self.bad_char_shift_map_rev, self.period, 0)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteReverse;
+    ///
+    /// let bmb = BMByteReverse::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 4], bmb.rfind_full_in("coocoocoocoo", 2));
+    /// ```
+    pub fn rfind_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        rfind_full_with_period(
+            text,
+            &self.pattern,
+            &self.bad_char_shift_map_rev,
+            self.period,
+            limit,
+        )
+    }
+
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteReverse;
+    ///
+    /// let bmb = BMByteReverse::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 1], bmb.rfind_all_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 0)
+    }
+
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteReverse;
+    ///
+    /// let bmb = BMByteReverse::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(Some(7), bmb.rfind_first_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
+        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 1).first().copied()
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteReverse;
+    ///
+    /// let bmb = BMByteReverse::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7], bmb.rfind_in("coocoocoocoo", 1));
+    /// ```
+    pub fn rfind_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, limit)
+    }
+}
+
+impl BMByte {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 4, 7], bmb.find_full_all_in("coocoocoocoo"));
+    /// ```
+    pub fn find_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_forward(text, 0, true)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack). If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 4], bmb.find_full_in("coocoocoocoo", 2));
+    /// ```
+    pub fn find_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_forward(text, limit, true)
+    }
+}
+
+impl BMByte {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 4, 1], bmb.rfind_full_all_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_backward(text, 0, true)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 4], bmb.rfind_full_in("coocoocoocoo", 2));
+    /// ```
+    pub fn rfind_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_backward(text, limit, true)
+    }
+}
+
+#[inline]
+pub fn find_full<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    limit: usize,
+) -> Vec<usize> {
+    find_full_with_period(text, pattern, bad_char_shift_map, 0, limit)
+}
+
+/// Search forward and include the overlapping sub-sequences. `period` must be the smallest period of the pattern (or `0` if it is unknown). After a match, if the pattern can be shifted by its period, the bytes known to match are not compared again.
+pub fn find_full_with_period<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    period: usize,
+    limit: usize,
+) -> Vec<usize> {
+    scan_find_full_with_period(text, pattern, bad_char_shift_map, period, limit)
+}
+
+fn scan_find_full_with_period<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    period: usize,
+    limit: usize,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    let mut shift = 0;
+
+    let end_index = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    // the number of the leading bytes in the current window known to match the pattern
+    let mut memory = 0;
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate().skip(memory).rev() {
+            if text.value_at(shift + i) != pc {
+                let p = shift + pattern_len;
+                if p == text_len {
+                    break 'outer;
+                }
+                shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize].max({
+                    let c = text.value_at(p);
+
+                    if c == last_pattern_char {
+                        1
+                    } else {
+                        bad_char_shift_map[c as usize] + 1
+                    }
+                });
+                memory = 0;
+                if shift > end_index {
+                    break 'outer;
+                }
+                continue 'outer;
+            }
+        }
+        result.push(shift);
+
+        if shift == end_index {
+            break;
+        }
+
+        if result.len() == limit {
+            break;
+        }
+
+        let s = bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize].max({
+            let c = text.value_at(shift + pattern_len);
+
+            if c == last_pattern_char {
+                1
+            } else {
+                bad_char_shift_map[c as usize] + 1
+            }
+        });
+        if s <= period {
+            shift += period;
+            memory = pattern_len - period;
+        } else {
+            shift += s;
+            memory = 0;
+        }
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+#[inline]
+pub fn rfind_full<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    limit: usize,
+) -> Vec<usize> {
+    rfind_full_with_period(text, pattern, bad_char_shift_map, 0, limit)
+}
+
+/// Search backward and include the overlapping sub-sequences. `period` must be the smallest period of the pattern (or `0` if it is unknown). After a match, if the pattern can be shifted by its period, the bytes known to match are not compared again.
+pub fn rfind_full_with_period<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    period: usize,
+    limit: usize,
+) -> Vec<usize> {
+    scan_rfind_full_with_period(text, pattern, bad_char_shift_map, period, limit)
+}
+
+fn scan_rfind_full_with_period<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    period: usize,
+    limit: usize,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+
+    let first_pattern_char = pattern.value_at(0);
+
+    let mut shift = text_len - 1;
+
+    let start_index = pattern_len_dec;
+
+    let mut result = vec![];
+
+    // the number of the trailing bytes in the current window known to match the pattern
+    let mut memory = 0;
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate().take(pattern_len - memory) {
+            if text.value_at(shift - pattern_len_dec + i) != pc {
+                if shift < pattern_len {
+                    break 'outer;
+                }
+                let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize].max({
+                    let c = text.value_at(shift - pattern_len);
+
+                    if c == first_pattern_char {
+                        1
+                    } else {
+                        bad_char_shift_map[c as usize] + 1
+                    }
+                });
+                if shift < s {
+                    break 'outer;
+                }
+                shift -= s;
+                memory = 0;
+                if shift < start_index {
+                    break 'outer;
+                }
+                continue 'outer;
+            }
+        }
+        result.push(shift - pattern_len_dec);
+
+        if shift == start_index {
+            break;
+        }
+
+        if result.len() == limit {
+            break;
+        }
+
+        let mut s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize].max({
+            let c = text.value_at(shift - pattern_len);
+
+            if c == first_pattern_char {
+                1
+            } else {
+                bad_char_shift_map[c as usize] + 1
+            }
+        });
+        if s <= period {
+            s = period;
+            memory = pattern_len - period;
+        } else {
+            memory = 0;
+        }
+        if shift < s {
+            break;
+        }
+        shift -= s;
+        if shift < start_index {
+            break;
+        }
+    }
+
+    result
+}
+
+impl BMByte {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
+    /// ```
+    pub fn find_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_forward(text, 0, false)
+    }
+
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(Some(1), bmb.find_first_in("coocoocoocoo"));
+    /// ```
+    pub fn find_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
+        self.find_forward(text, 1, false).first().copied()
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1], bmb.find_in("coocoocoocoo", 1));
+    /// ```
+    pub fn find_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_forward(text, limit, false)
+    }
+}
+
+impl BMByte {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 1], bmb.rfind_all_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_backward(text, 0, false)
+    }
+
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(Some(7), bmb.rfind_first_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
+        self.find_backward(text, 1, false).first().copied()
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7], bmb.rfind_in("coocoocoocoo", 1));
+    /// ```
+    pub fn rfind_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_backward(text, limit, false)
+    }
+}
+
+pub fn find<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    limit: usize,
+) -> Vec<usize> {
+    scan_find(text, pattern, bad_char_shift_map, limit)
+}
+
+fn scan_find<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    limit: usize,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    let mut shift = 0;
+
+    let end_index = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate().rev() {
+            if text.value_at(shift + i) != pc {
+                let p = shift + pattern_len;
+                if p == text_len {
+                    break 'outer;
+                }
+                shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize].max({
+                    let c = text.value_at(p);
+
+                    if c == last_pattern_char {
+                        1
+                    } else {
+                        bad_char_shift_map[c as usize] + 1
+                    }
+                });
+                if shift > end_index {
+                    break 'outer;
+                }
+                continue 'outer;
+            }
+        }
+        result.push(shift);
+
+        if shift == end_index {
+            break;
+        }
+
+        if result.len() == limit {
+            break;
+        }
+
+        shift += pattern_len;
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+pub fn rfind<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    limit: usize,
+) -> Vec<usize> {
+    scan_rfind(text, pattern, bad_char_shift_map, limit)
+}
+
+fn scan_rfind<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    limit: usize,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+
+    let first_pattern_char = pattern.value_at(0);
+
+    let mut shift = text_len - 1;
+
+    let start_index = pattern_len_dec;
+
+    let mut result = vec![];
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate() {
+            if text.value_at(shift - pattern_len_dec + i) != pc {
+                if shift < pattern_len {
+                    break 'outer;
+                }
+                let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize].max({
+                    let c = text.value_at(shift - pattern_len);
+
+                    if c == first_pattern_char {
+                        1
+                    } else {
+                        bad_char_shift_map[c as usize] + 1
+                    }
+                });
+                if shift < s {
+                    break 'outer;
+                }
+                shift -= s;
+                if shift < start_index {
+                    break 'outer;
+                }
+                continue 'outer;
+            }
+        }
+        result.push(shift - pattern_len_dec);
+
+        if shift == start_index {
+            break;
+        }
+
+        if result.len() == limit {
+            break;
+        }
+
+        shift -= pattern_len;
+        if shift < start_index {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Search forward with the bad character rule of Boyer-Moore-MagicLen and the good suffix rule of Boyer-Moore. If `full` is `true`, the overlapping sub-sequences are included.
+pub fn find_with_good_suffix<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    good_suffix_shift_map: &BMByteGoodSuffixShiftMap,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    scan_find_with_good_suffix(
+        text,
+        pattern,
+        bad_char_shift_map,
+        good_suffix_shift_map,
+        limit,
+        full,
+    )
+}
+
+fn scan_find_with_good_suffix<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    good_suffix_shift_map: &BMByteGoodSuffixShiftMap,
     limit: usize,
+    full: bool,
 ) -> Vec<usize> {
     let text_len = text.len();
     let pattern_len = pattern.len();
@@ -360,33 +1765,151 @@ pub fn rfind_full<TT: BMByteSearchable, TP: BMByteSearchable>(
 
     let pattern_len_dec = pattern_len - 1;
 
-    let first_pattern_char = pattern.value_at(0);
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
 
-    let mut shift = text_len - 1;
+    let mut shift = 0;
 
-    let start_index = pattern_len_dec;
+    let end_index = text_len - pattern_len;
 
     let mut result = vec![];
 
+    // the number of the leading bytes in the current window known to match the pattern
+    let mut memory = 0;
+
     'outer: loop {
-        for (i, pc) in pattern.iter().copied().enumerate() {
-            if text.value_at(shift - pattern_len_dec + i) != pc {
-                if shift < pattern_len {
+        for (i, pc) in pattern.iter().copied().enumerate().skip(memory).rev() {
+            if text.value_at(shift + i) != pc {
+                let p = shift + pattern_len;
+                if p == text_len {
                     break 'outer;
                 }
-                let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize].max({
-                    let c = text.value_at(shift - pattern_len);
+                shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize]
+                    .max({
+                        let c = text.value_at(p);
+
+                        if c == last_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map[c as usize] + 1
+                        }
+                    })
+                    .max(good_suffix_shift_map[i]);
+                memory = 0;
+                if shift > end_index {
+                    break 'outer;
+                }
+                continue 'outer;
+            }
+        }
+        result.push(shift);
 
-                    if c == first_pattern_char {
+        if shift == end_index {
+            break;
+        }
+
+        if result.len() == limit {
+            break;
+        }
+
+        if full {
+            // the shift by the good suffix rule after a match is the smallest period of the pattern
+            let period = good_suffix_shift_map[0];
+
+            let s = bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize]
+                .max({
+                    let c = text.value_at(shift + pattern_len);
+
+                    if c == last_pattern_char {
                         1
                     } else {
                         bad_char_shift_map[c as usize] + 1
                     }
-                });
+                })
+                .max(period);
+
+            shift += s;
+
+            memory = if s == period { pattern_len - period } else { 0 };
+        } else {
+            shift += pattern_len;
+        }
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Search backward with the bad character rule of Boyer-Moore-MagicLen and the good suffix rule of Boyer-Moore. If `full` is `true`, the overlapping sub-sequences are included.
+pub fn rfind_with_good_suffix<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    good_suffix_shift_map: &BMByteGoodSuffixShiftMapRev,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    scan_rfind_with_good_suffix(
+        text,
+        pattern,
+        bad_char_shift_map,
+        good_suffix_shift_map,
+        limit,
+        full,
+    )
+}
+
+fn scan_rfind_with_good_suffix<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    good_suffix_shift_map: &BMByteGoodSuffixShiftMapRev,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+
+    let first_pattern_char = pattern.value_at(0);
+
+    let mut shift = text_len - 1;
+
+    let start_index = pattern_len_dec;
+
+    let mut result = vec![];
+
+    // the number of the trailing bytes in the current window known to match the pattern
+    let mut memory = 0;
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate().take(pattern_len - memory) {
+            if text.value_at(shift - pattern_len_dec + i) != pc {
+                if shift < pattern_len {
+                    break 'outer;
+                }
+                let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize]
+                    .max({
+                        let c = text.value_at(shift - pattern_len);
+
+                        if c == first_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map[c as usize] + 1
+                        }
+                    })
+                    .max(good_suffix_shift_map[i]);
                 if shift < s {
                     break 'outer;
                 }
                 shift -= s;
+                memory = 0;
                 if shift < start_index {
                     break 'outer;
                 }
@@ -403,15 +1926,28 @@ pub fn rfind_full<TT: BMByteSearchable, TP: BMByteSearchable>(
             break;
         }
 
-        let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize].max({
-            let c = text.value_at(shift - pattern_len);
+        let s = if full {
+            // the shift by the good suffix rule after a match is the smallest period of the pattern
+            let period = good_suffix_shift_map[pattern_len_dec];
 
-            if c == first_pattern_char {
-                1
-            } else {
-                bad_char_shift_map[c as usize] + 1
-            }
-        });
+            let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize]
+                .max({
+                    let c = text.value_at(shift - pattern_len);
+
+                    if c == first_pattern_char {
+                        1
+                    } else {
+                        bad_char_shift_map[c as usize] + 1
+                    }
+                })
+                .max(period);
+
+            memory = if s == period { pattern_len - period } else { 0 };
+
+            s
+        } else {
+            pattern_len
+        };
         if shift < s {
             break;
         }
@@ -424,93 +1960,52 @@ pub fn rfind_full<TT: BMByteSearchable, TP: BMByteSearchable>(
     result
 }
 
-impl BMByte {
-    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap.
-    ///
-    /// ```
-    /// use boyer_moore_magiclen::BMByte;
-    ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
-    ///
-    /// assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
-    /// ```
-    pub fn find_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
-        find(text, &self.pattern, &self.bad_char_shift_map, 0)
-    }
-
-    /// Find and return the position of the first matched sub-sequence in any text (the haystack).
-    ///
-    /// ```
-    /// use boyer_moore_magiclen::BMByte;
-    ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
-    ///
-    /// assert_eq!(Some(1), bmb.find_first_in("coocoocoocoo"));
-    /// ```
-    pub fn find_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
-        find(text, &self.pattern, &self.bad_char_shift_map, 1).first().copied()
-    }
+/// Search forward with Boyer-Moore-MagicLen, whose Horspool half of the shift is decided by the bigram shift map. If `full` is `true`, the overlapping sub-sequences are included. If the pattern is shorter than two bytes, nothing is found.
+pub fn find_with_bigram<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    bigram_shift_map: &BMByteBigramShiftMap,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    bigram::find(text, pattern, bad_char_shift_map, bigram_shift_map, None, None, limit, full)
+}
 
-    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found.
-    ///
-    /// ```
-    /// use boyer_moore_magiclen::BMByte;
-    ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
-    ///
-    /// assert_eq!(vec![1], bmb.find_in("coocoocoocoo", 1));
-    /// ```
-    pub fn find_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        find(text, &self.pattern, &self.bad_char_shift_map, limit)
-    }
+/// Search backward with Boyer-Moore-MagicLen, whose Horspool half of the shift is decided by the bigram shift map. If `full` is `true`, the overlapping sub-sequences are included. If the pattern is shorter than two bytes, nothing is found.
+pub fn rfind_with_bigram<TT: BMByteSearchable, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    bigram_shift_map: &BMByteBigramShiftMapRev,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    bigram::rfind(text, pattern, bad_char_shift_map, bigram_shift_map, None, None, limit, full)
 }
 
-impl BMByte {
-    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head.
-    ///
-    /// ```
-    /// use boyer_moore_magiclen::BMByte;
-    ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
-    ///
-    /// assert_eq!(vec![7, 1], bmb.rfind_all_in("coocoocoocoo"));
-    /// ```
-    pub fn rfind_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
-        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 0)
-    }
+/// The minimum length of a pattern for the bigram shift maps to be used. Shorter patterns cannot shift far enough to pay for building 65536 entries.
+const BIGRAM_MIN_PATTERN_LEN: usize = 16;
 
-    /// Find and return the position of the first matched sub-sequence in any text (the haystack) from its tail to its head.
-    ///
-    /// ```
-    /// use boyer_moore_magiclen::BMByte;
-    ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
-    ///
-    /// assert_eq!(Some(7), bmb.rfind_first_in("coocoocoocoo"));
-    /// ```
-    pub fn rfind_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
-        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 1).first().copied()
-    }
+/// The maximum number of byte comparisons per byte of the text before a linear search switches to the Two-Way algorithm.
+const LINEAR_COMPARISON_BUDGET_FACTOR: usize = 2;
 
-    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
-    ///
-    /// ```
-    /// use boyer_moore_magiclen::BMByte;
-    ///
-    /// let bmb = BMByte::from("oocoo").unwrap();
-    ///
-    /// assert_eq!(vec![7], bmb.rfind_in("coocoocoocoo", 1));
-    /// ```
-    pub fn rfind_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, limit)
-    }
+#[inline]
+fn create_two_way_rev(pattern: &[u8]) -> TwoWay {
+    let pattern_len = pattern.len();
+
+    TwoWay::new(pattern_len, |i| pattern[pattern_len - 1 - i])
 }
 
-pub fn find<TT: BMByteSearchable, TP: BMByteSearchable>(
+/// Search forward with Boyer-Moore-MagicLen (and the good suffix rule if the map is given), but switch to the Two-Way algorithm once the number of byte comparisons exceeds the budget.
+fn find_linear<TT: ScanText, TP: BMByteSearchable>(
     text: TT,
     pattern: TP,
     bad_char_shift_map: &BMByteBadCharShiftMap,
+    good_suffix_shift_map: Option<&BMByteGoodSuffixShiftMap>,
+    two_way: &TwoWay,
     limit: usize,
+    full: bool,
 ) -> Vec<usize> {
     let text_len = text.len();
     let pattern_len = pattern.len();
@@ -529,52 +2024,88 @@ pub fn find<TT: BMByteSearchable, TP: BMByteSearchable>(
 
     let mut result = vec![];
 
+    let budget = text_len * LINEAR_COMPARISON_BUDGET_FACTOR;
+    let mut comparisons = 0;
+
     'outer: loop {
         for (i, pc) in pattern.iter().copied().enumerate().rev() {
+            comparisons += 1;
+
             if text.value_at(shift + i) != pc {
                 let p = shift + pattern_len;
                 if p == text_len {
                     break 'outer;
                 }
-                shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize].max({
-                    let c = text.value_at(p);
-
-                    if c == last_pattern_char {
-                        1
-                    } else {
-                        bad_char_shift_map[c as usize] + 1
-                    }
-                });
+                shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize]
+                    .max({
+                        let c = text.value_at(p);
+
+                        if c == last_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map[c as usize] + 1
+                        }
+                    })
+                    .max(good_suffix_shift_map.map(|m| m[i]).unwrap_or(1));
                 if shift > end_index {
                     break 'outer;
                 }
+                if comparisons > budget {
+                    break;
+                }
                 continue 'outer;
             }
         }
-        result.push(shift);
 
-        if shift == end_index {
-            break;
-        }
+        if comparisons <= budget {
+            result.push(shift);
 
-        if result.len() == limit {
-            break;
-        }
+            if shift == end_index {
+                break;
+            }
 
-        shift += pattern_len;
-        if shift > end_index {
-            break;
+            if result.len() == limit {
+                break;
+            }
+
+            if full {
+                shift += bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize]
+                    .max({
+                        let c = text.value_at(shift + pattern_len);
+
+                        if c == last_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map[c as usize] + 1
+                        }
+                    })
+                    .max(good_suffix_shift_map.map(|m| m[0]).unwrap_or(1));
+            } else {
+                shift += pattern_len;
+            }
+            if shift > end_index {
+                break;
+            }
+            continue;
         }
+
+        classic::two_way_find(two_way, &text, &pattern, shift, limit, full, &mut result);
+
+        break;
     }
 
     result
 }
 
-pub fn rfind<TT: BMByteSearchable, TP: BMByteSearchable>(
+/// Search backward with Boyer-Moore-MagicLen (and the good suffix rule if the map is given), but switch to the Two-Way algorithm once the number of byte comparisons exceeds the budget.
+fn rfind_linear<TT: ScanText, TP: BMByteSearchable>(
     text: TT,
     pattern: TP,
     bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    good_suffix_shift_map: Option<&BMByteGoodSuffixShiftMapRev>,
+    two_way: &TwoWay,
     limit: usize,
+    full: bool,
 ) -> Vec<usize> {
     let text_len = text.len();
     let pattern_len = pattern.len();
@@ -593,21 +2124,28 @@ pub fn rfind<TT: BMByteSearchable, TP: BMByteSearchable>(
 
     let mut result = vec![];
 
+    let budget = text_len * LINEAR_COMPARISON_BUDGET_FACTOR;
+    let mut comparisons = 0;
+
     'outer: loop {
         for (i, pc) in pattern.iter().copied().enumerate() {
+            comparisons += 1;
+
             if text.value_at(shift - pattern_len_dec + i) != pc {
                 if shift < pattern_len {
                     break 'outer;
                 }
-                let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize].max({
-                    let c = text.value_at(shift - pattern_len);
-
-                    if c == first_pattern_char {
-                        1
-                    } else {
-                        bad_char_shift_map[c as usize] + 1
-                    }
-                });
+                let s = bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize]
+                    .max({
+                        let c = text.value_at(shift - pattern_len);
+
+                        if c == first_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map[c as usize] + 1
+                        }
+                    })
+                    .max(good_suffix_shift_map.map(|m| m[i]).unwrap_or(1));
                 if shift < s {
                     break 'outer;
                 }
@@ -615,23 +2153,60 @@ pub fn rfind<TT: BMByteSearchable, TP: BMByteSearchable>(
                 if shift < start_index {
                     break 'outer;
                 }
+                if comparisons > budget {
+                    break;
+                }
                 continue 'outer;
             }
         }
-        result.push(shift - pattern_len_dec);
 
-        if shift == start_index {
-            break;
-        }
+        if comparisons <= budget {
+            result.push(shift - pattern_len_dec);
 
-        if result.len() == limit {
-            break;
-        }
+            if shift == start_index {
+                break;
+            }
 
-        shift -= pattern_len;
-        if shift < start_index {
-            break;
+            if result.len() == limit {
+                break;
+            }
+
+            let s = if full {
+                bad_char_shift_map[text.value_at(shift - pattern_len_dec) as usize]
+                    .max({
+                        let c = text.value_at(shift - pattern_len);
+
+                        if c == first_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map[c as usize] + 1
+                        }
+                    })
+                    .max(good_suffix_shift_map.map(|m| m[pattern_len_dec]).unwrap_or(1))
+            } else {
+                pattern_len
+            };
+            if shift < s {
+                break;
+            }
+            shift -= s;
+            if shift < start_index {
+                break;
+            }
+            continue;
         }
+
+        classic::two_way_rfind(
+            two_way,
+            &text,
+            &pattern,
+            shift - pattern_len_dec,
+            limit,
+            full,
+            &mut result,
+        );
+
+        break;
     }
 
     result
@@ -0,0 +1,133 @@
+//! Searching any `tokio::io::AsyncRead` stream. Like `StreamSearcher`, the last `pattern.len() - 1` bytes are carried to the next read so that the matches straddling two reads are not missed, but the new bytes are searched as soon as they are read instead of waiting for the buffer to be filled.
+
+use core::{
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use super::{
+    stream::{StreamBuffer, DEFAULT_BUFFER_SIZE},
+    BMByte,
+};
+
+/// Search a `BMByte` pattern in any `tokio::io::AsyncRead` stream, without loading the whole stream into memory. It is a `Stream` of the absolute positions (from the head of the stream) of the matched sub-sequences. If an I/O error occurs, the error is yielded and the stream ends.
+///
+/// ```
+/// use boyer_moore_magiclen::{AsyncStreamSearcher, BMByte};
+/// use futures_core::Stream;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let bmb = BMByte::from("oocoo").unwrap();
+///
+/// let reader = "coocoocoocoo".as_bytes();
+///
+/// let mut searcher = AsyncStreamSearcher::new(&bmb, reader);
+///
+/// let mut result = Vec::new();
+///
+/// while let Some(position) =
+///     std::future::poll_fn(|cx| std::pin::Pin::new(&mut searcher).poll_next(cx)).await
+/// {
+///     result.push(position.unwrap());
+/// }
+///
+/// assert_eq!(vec![1, 7], result);
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct AsyncStreamSearcher<'a, R: AsyncRead + Unpin> {
+    bmb:         &'a BMByte,
+    reader:      R,
+    full:        bool,
+    buffer_size: usize,
+    buffer:      StreamBuffer,
+    finished:    bool,
+}
+
+impl<'a, R: AsyncRead + Unpin> AsyncStreamSearcher<'a, R> {
+    /// Create an `AsyncStreamSearcher` instance to search a `BMByte` pattern in a stream. The overlapping matches are not included by default.
+    #[inline]
+    pub fn new(bmb: &'a BMByte, reader: R) -> AsyncStreamSearcher<'a, R> {
+        AsyncStreamSearcher {
+            bmb,
+            reader,
+            full: false,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            buffer: StreamBuffer::default(),
+            finished: false,
+        }
+    }
+
+    /// Set whether to include the overlapping matches, like the `find_full_all_in` method instead of the `find_all_in` method.
+    #[inline]
+    pub fn full(mut self, full: bool) -> Self {
+        self.full = full;
+
+        self
+    }
+
+    /// Set the maximum number of bytes read from the stream at a time. The default size is 64 KiB. A size of `0` is treated as `1`.
+    #[inline]
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+
+        self
+    }
+
+    /// Get the number of bytes which have been read from the stream.
+    #[inline]
+    pub fn bytes_read(&self) -> u64 {
+        self.buffer.bytes_read()
+    }
+
+    /// Unwrap this `AsyncStreamSearcher`, returning the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<'a, R: AsyncRead + Unpin> Stream for AsyncStreamSearcher<'a, R> {
+    type Item = std::io::Result<u64>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(position) = this.buffer.results.pop_front() {
+                return Poll::Ready(Some(Ok(position)));
+            }
+
+            if this.finished {
+                return Poll::Ready(None);
+            }
+
+            let bytes = &mut this.buffer.bytes;
+
+            let old_len = bytes.len();
+
+            bytes.resize(old_len + this.buffer_size, 0);
+
+            let mut read_buf = ReadBuf::new(&mut bytes[old_len..]);
+
+            let result = Pin::new(&mut this.reader).poll_read(cx, &mut read_buf);
+
+            let c = read_buf.filled().len();
+
+            bytes.truncate(old_len + c);
+
+            match ready!(result) {
+                Ok(()) if c == 0 => this.finished = true,
+                Ok(()) => this.buffer.search(this.bmb, this.full),
+                Err(err) => {
+                    this.finished = true;
+
+                    return Poll::Ready(Some(Err(err)));
+                },
+            }
+        }
+    }
+}
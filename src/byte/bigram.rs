@@ -0,0 +1,224 @@
+//! The scan loops of Boyer-Moore-MagicLen with the bigram shift maps (Wu-Manber style with blocks of two bytes). The Horspool half of the shift is decided by the last two bytes of the window instead of the last byte, while the Sunday half is unchanged.
+
+use alloc::vec::Vec;
+
+use super::{
+    classic, BMByteBadCharShiftMap, BMByteBadCharShiftMapRev, BMByteBigramShiftMap,
+    BMByteBigramShiftMapRev, BMByteGoodSuffixShiftMap, BMByteGoodSuffixShiftMapRev,
+    BMByteSearchable, ScanText,
+};
+use crate::two_way::TwoWay;
+
+#[inline]
+pub(super) fn bigram_index(a: u8, b: u8) -> usize {
+    ((a as usize) << 8) | b as usize
+}
+
+/// Search forward. The good suffix rule is also used if its map is given, and the rest of the text is searched by the Two-Way algorithm once the comparison budget is exceeded if `two_way` is given. The pattern must not be shorter than two bytes.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn find<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    bigram_shift_map: &BMByteBigramShiftMap,
+    good_suffix_shift_map: Option<&BMByteGoodSuffixShiftMap>,
+    two_way: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len < 2 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    let mut shift = 0;
+
+    let end_index = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    let budget = classic::budget(text_len, two_way);
+    let mut comparisons = 0;
+
+    let next_shift = |shift: usize, i: usize| {
+        let window_end = shift + pattern_len_dec;
+
+        (bigram_shift_map[bigram_index(text.value_at(window_end - 1), text.value_at(window_end))]
+            as usize)
+            .max({
+                let c = text.value_at(shift + pattern_len);
+
+                if c == last_pattern_char {
+                    1
+                } else {
+                    bad_char_shift_map[c as usize] + 1
+                }
+            })
+            .max(good_suffix_shift_map.map(|m| m[i]).unwrap_or(1))
+    };
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate().rev() {
+            comparisons += 1;
+
+            if text.value_at(shift + i) != pc {
+                if shift == end_index {
+                    break 'outer;
+                }
+                shift += next_shift(shift, i);
+                if shift > end_index {
+                    break 'outer;
+                }
+                if comparisons > budget {
+                    break;
+                }
+                continue 'outer;
+            }
+        }
+
+        if comparisons <= budget {
+            result.push(shift);
+
+            if shift == end_index {
+                break;
+            }
+
+            if result.len() == limit {
+                break;
+            }
+
+            shift += if full { next_shift(shift, 0) } else { pattern_len };
+            if shift > end_index {
+                break;
+            }
+            continue;
+        }
+
+        if let Some(two_way) = two_way {
+            classic::two_way_find(two_way, &text, &pattern, shift, limit, full, &mut result);
+        }
+
+        break;
+    }
+
+    result
+}
+
+/// Search backward. The good suffix rule is also used if its map is given, and the rest of the text is searched by the Two-Way algorithm (built from the reversed pattern) once the comparison budget is exceeded if `two_way_rev` is given. The pattern must not be shorter than two bytes.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn rfind<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    bigram_shift_map: &BMByteBigramShiftMapRev,
+    good_suffix_shift_map: Option<&BMByteGoodSuffixShiftMapRev>,
+    two_way_rev: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len < 2 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+
+    let first_pattern_char = pattern.value_at(0);
+
+    let mut shift = text_len - 1;
+
+    let start_index = pattern_len_dec;
+
+    let mut result = vec![];
+
+    let budget = classic::budget(text_len, two_way_rev);
+    let mut comparisons = 0;
+
+    let next_shift = |shift: usize, i: usize| {
+        let window_start = shift - pattern_len_dec;
+
+        (bigram_shift_map
+            [bigram_index(text.value_at(window_start), text.value_at(window_start + 1))]
+            as usize)
+            .max({
+                let c = text.value_at(shift - pattern_len);
+
+                if c == first_pattern_char {
+                    1
+                } else {
+                    bad_char_shift_map[c as usize] + 1
+                }
+            })
+            .max(good_suffix_shift_map.map(|m| m[i]).unwrap_or(1))
+    };
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate() {
+            comparisons += 1;
+
+            if text.value_at(shift - pattern_len_dec + i) != pc {
+                if shift == start_index {
+                    break 'outer;
+                }
+                let s = next_shift(shift, i);
+                if shift < s {
+                    break 'outer;
+                }
+                shift -= s;
+                if shift < start_index {
+                    break 'outer;
+                }
+                if comparisons > budget {
+                    break;
+                }
+                continue 'outer;
+            }
+        }
+
+        if comparisons <= budget {
+            result.push(shift - pattern_len_dec);
+
+            if shift == start_index {
+                break;
+            }
+
+            if result.len() == limit {
+                break;
+            }
+
+            let s = if full { next_shift(shift, pattern_len_dec) } else { pattern_len };
+            if shift < s {
+                break;
+            }
+            shift -= s;
+            if shift < start_index {
+                break;
+            }
+            continue;
+        }
+
+        if let Some(two_way_rev) = two_way_rev {
+            classic::two_way_rfind(
+                two_way_rev,
+                &text,
+                &pattern,
+                shift - pattern_len_dec,
+                limit,
+                full,
+                &mut result,
+            );
+        }
+
+        break;
+    }
+
+    result
+}
@@ -0,0 +1,140 @@
+//! The glue between `BMByte` and the bit-parallel algorithms (`Algorithm::ShiftOr` and `Algorithm::Bndm`).
+
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+use super::{Algorithm, ScanText};
+use crate::bit_parallel;
+
+/// The forward masks and the backward masks of a pattern which is not longer than `bit_parallel::MAX_PATTERN_LEN`.
+pub(super) struct BMByteBitMasks {
+    forward:  [u64; 256],
+    backward: [u64; 256],
+}
+
+impl Debug for BMByteBitMasks {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        debug_helper::impl_debug_for_struct!(BMByteBitMasks, f, self, let .forward = self.forward.as_ref(), let .backward = self.backward.as_ref());
+    }
+}
+
+impl BMByteBitMasks {
+    /// Create the masks of a pattern. If the pattern is empty or longer than `bit_parallel::MAX_PATTERN_LEN`, `None` is returned.
+    pub(super) fn create_bit_masks(pattern: &[u8]) -> Option<BMByteBitMasks> {
+        let pattern_len = pattern.len();
+
+        if pattern_len == 0 || pattern_len > bit_parallel::MAX_PATTERN_LEN {
+            return None;
+        }
+
+        let mut forward = [0; 256];
+        let mut backward = [0; 256];
+
+        bit_parallel::for_each_mask(
+            pattern_len,
+            |i| pattern[i],
+            |c, f, b| {
+                forward[c as usize] |= f;
+                backward[c as usize] |= b;
+            },
+        );
+
+        Some(BMByteBitMasks {
+            forward,
+            backward,
+        })
+    }
+}
+
+/// Search forward by `Algorithm::ShiftOr` or `Algorithm::Bndm`.
+pub(super) fn find<T: ScanText>(
+    algorithm: Algorithm,
+    text: T,
+    pattern_len: usize,
+    masks: &BMByteBitMasks,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+
+    let mut result = vec![];
+
+    if text_len < pattern_len {
+        return result;
+    }
+
+    if algorithm == Algorithm::ShiftOr {
+        bit_parallel::shift_or_find(
+            text_len,
+            |i| text.value_at(i),
+            pattern_len,
+            |c| masks.forward[c as usize],
+            limit,
+            full,
+            &mut result,
+        );
+    } else {
+        bit_parallel::bndm_find(
+            text_len,
+            |i| text.value_at(i),
+            pattern_len,
+            |c| masks.backward[c as usize],
+            limit,
+            full,
+            &mut result,
+        );
+    }
+
+    result
+}
+
+/// Search backward by `Algorithm::ShiftOr` or `Algorithm::Bndm`. The reversed text is searched for the reversed pattern, whose forward masks are the backward masks of the pattern.
+pub(super) fn rfind<T: ScanText>(
+    algorithm: Algorithm,
+    text: T,
+    pattern_len: usize,
+    masks: &BMByteBitMasks,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+
+    let mut result = vec![];
+
+    if text_len < pattern_len {
+        return result;
+    }
+
+    let text_len_dec = text_len - 1;
+
+    if algorithm == Algorithm::ShiftOr {
+        bit_parallel::shift_or_find(
+            text_len,
+            |i| text.value_at(text_len_dec - i),
+            pattern_len,
+            |c| masks.backward[c as usize],
+            limit,
+            full,
+            &mut result,
+        );
+    } else {
+        bit_parallel::bndm_find(
+            text_len,
+            |i| text.value_at(text_len_dec - i),
+            pattern_len,
+            |c| masks.forward[c as usize],
+            limit,
+            full,
+            &mut result,
+        );
+    }
+
+    let end_index = text_len - pattern_len;
+
+    for i in result.iter_mut() {
+        *i = end_index - *i;
+    }
+
+    result
+}
@@ -0,0 +1,138 @@
+//! Reading `std::io::BufRead` streams until a multi-byte delimiter. The delimiters straddling two `fill_buf` calls are found by searching the last `pattern.len() - 1` bytes appended so far together with the first `pattern.len() - 1` bytes of the new buffer.
+
+use std::io::{BufRead, ErrorKind, Result};
+
+use super::BMByte;
+
+/// An extension trait for `std::io::BufRead` to read until a multi-byte delimiter.
+pub trait BMByteBufReadExt: BufRead {
+    /// Read all bytes into `buf` until the pattern of `bmb` (the delimiter) or the end of the stream is reached. Like `BufRead::read_until`, the delimiter (if found) is appended to `buf`, and the number of bytes read is returned.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, BMByteBufReadExt};
+    ///
+    /// let bmb = BMByte::from("\r\n").unwrap();
+    ///
+    /// let mut reader = "a: 1\r\nb: 2".as_bytes();
+    /// let mut buf = Vec::new();
+    ///
+    /// assert_eq!(6, reader.read_until_pattern(&bmb, &mut buf).unwrap());
+    /// assert_eq!(b"a: 1\r\n", buf.as_slice());
+    ///
+    /// buf.clear();
+    ///
+    /// assert_eq!(4, reader.read_until_pattern(&bmb, &mut buf).unwrap());
+    /// assert_eq!(b"b: 2", buf.as_slice());
+    ///
+    /// buf.clear();
+    ///
+    /// assert_eq!(0, reader.read_until_pattern(&bmb, &mut buf).unwrap());
+    /// ```
+    fn read_until_pattern(&mut self, bmb: &BMByte, buf: &mut Vec<u8>) -> Result<usize> {
+        let pattern_len_dec = bmb.pattern.len() - 1;
+
+        let start_len = buf.len();
+
+        loop {
+            let available = match self.fill_buf() {
+                Ok(available) => available,
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+
+            if available.is_empty() {
+                return Ok(buf.len() - start_len);
+            }
+
+            // the delimiter may start in the bytes appended before
+            let tail_len = pattern_len_dec.min(buf.len() - start_len);
+
+            let end = if tail_len > 0 {
+                let mut window = buf[buf.len() - tail_len..].to_vec();
+
+                window.extend_from_slice(&available[..pattern_len_dec.min(available.len())]);
+
+                bmb.find_first_in(&window).map(|i| i + pattern_len_dec + 1 - tail_len)
+            } else {
+                None
+            }
+            .or_else(|| bmb.find_first_in(available).map(|i| i + pattern_len_dec + 1));
+
+            let (used, found) = match end {
+                Some(end) => (end, true),
+                None => (available.len(), false),
+            };
+
+            buf.extend_from_slice(&available[..used]);
+            self.consume(used);
+
+            if found {
+                return Ok(buf.len() - start_len);
+            }
+        }
+    }
+
+    /// Create an iterator over the records of this stream separated by the pattern of `bmb` (the delimiter). Like `BufRead::split`, the delimiters are not included in the records, and the bytes after the last delimiter are the last record if they are not empty.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, BMByteBufReadExt};
+    ///
+    /// let bmb = BMByte::from("\r\n\r\n").unwrap();
+    ///
+    /// let reader = "a\r\n\r\nb\r\nc\r\n\r\n\r\n\r\nd".as_bytes();
+    ///
+    /// let records: Vec<Vec<u8>> =
+    ///     reader.records(&bmb).map(|r| r.unwrap()).collect();
+    ///
+    /// assert_eq!(
+    ///     vec![b"a".to_vec(), b"b\r\nc".to_vec(), b"".to_vec(), b"d".to_vec()],
+    ///     records
+    /// );
+    /// ```
+    #[inline]
+    fn records(self, bmb: &BMByte) -> Records<'_, Self>
+    where
+        Self: Sized, {
+        Records {
+            bmb,
+            reader: self,
+        }
+    }
+}
+
+impl<B: BufRead + ?Sized> BMByteBufReadExt for B {}
+
+/// An iterator over the records of a `std::io::BufRead` stream separated by a multi-byte delimiter. It is created by the `BMByteBufReadExt::records` method.
+#[derive(Debug)]
+pub struct Records<'a, B> {
+    bmb:    &'a BMByte,
+    reader: B,
+}
+
+impl<'a, B: BufRead> Iterator for Records<'a, B> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = Vec::new();
+
+        match self.reader.read_until_pattern(self.bmb, &mut buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                let pattern_len = self.bmb.pattern.len();
+
+                // the delimiter may differ from the pattern in case
+                if buf.len() >= pattern_len
+                    && buf[buf.len() - pattern_len..]
+                        .iter()
+                        .zip(self.bmb.pattern.iter())
+                        .all(|(&c, &pc)| self.bmb.fold(c) == pc)
+                {
+                    buf.truncate(buf.len() - pattern_len);
+                }
+
+                Some(Ok(buf))
+            },
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
@@ -0,0 +1,685 @@
+//! The scan loops of the classic algorithms which can be selected by `Algorithm`. They share the bad character shift maps of Boyer-Moore-MagicLen:
+//!
+//! * The Horspool shift is `bad_char_shift_map[c]` where `c` is the last byte of the window.
+//! * The Sunday (Quick Search) shift is `1` if `c` is the last byte of the pattern, or `bad_char_shift_map[c] + 1` otherwise, where `c` is the byte right after the window.
+//!
+//! (Boyer-Moore-MagicLen takes the maximum of both.)
+
+use alloc::vec::Vec;
+
+use super::{
+    Algorithm, BMByteBadCharShiftMap, BMByteBadCharShiftMapRev, BMByteSearchable, ScanText,
+    LINEAR_COMPARISON_BUDGET_FACTOR,
+};
+use crate::two_way::TwoWay;
+
+/// The comparison budget before switching to the Two-Way algorithm. There is no budget if `two_way` is not given.
+#[inline]
+pub(super) fn budget(text_len: usize, two_way: Option<&TwoWay>) -> usize {
+    if two_way.is_some() {
+        text_len * LINEAR_COMPARISON_BUDGET_FACTOR
+    } else {
+        usize::MAX
+    }
+}
+
+/// Search the rest of the text forward by the Two-Way algorithm from the window starting at `shift`.
+#[inline]
+pub(super) fn two_way_find<TT: ScanText, TP: BMByteSearchable>(
+    two_way: &TwoWay,
+    text: &TT,
+    pattern: TP,
+    shift: usize,
+    limit: usize,
+    full: bool,
+    result: &mut Vec<usize>,
+) {
+    two_way.find(
+        text.len(),
+        |i| text.value_at(i),
+        pattern.len(),
+        |i| pattern.value_at(i),
+        shift,
+        limit,
+        full,
+        result,
+    );
+}
+
+/// Search the rest of the text backward by the Two-Way algorithm (built from the reversed pattern) from the window starting at `shift`.
+#[inline]
+pub(super) fn two_way_rfind<TT: ScanText, TP: BMByteSearchable>(
+    two_way_rev: &TwoWay,
+    text: &TT,
+    pattern: TP,
+    shift: usize,
+    limit: usize,
+    full: bool,
+    result: &mut Vec<usize>,
+) {
+    let text_len = text.len();
+    let text_len_dec = text_len - 1;
+    let pattern_len = pattern.len();
+    let pattern_len_dec = pattern_len - 1;
+    let end_index = text_len - pattern_len;
+
+    let mut rev_result = vec![];
+
+    two_way_rev.find(
+        text_len,
+        |i| text.value_at(text_len_dec - i),
+        pattern_len,
+        |i| pattern.value_at(pattern_len_dec - i),
+        end_index - shift,
+        if limit == 0 { 0 } else { limit - result.len() },
+        full,
+        &mut rev_result,
+    );
+
+    result.extend(rev_result.into_iter().map(|i| end_index - i));
+}
+
+/// Search forward by one of the classic algorithms. `Algorithm::MagicLen` and the bit-parallel algorithms are not handled here.
+pub(super) fn find<TT: ScanText, TP: BMByteSearchable>(
+    algorithm: Algorithm,
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    two_way: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    match algorithm {
+        Algorithm::MagicLen | Algorithm::ShiftOr | Algorithm::Bndm => unreachable!(),
+        Algorithm::Horspool => {
+            horspool_find(text, pattern, bad_char_shift_map, two_way, limit, full)
+        },
+        Algorithm::Sunday => sunday_find(text, pattern, bad_char_shift_map, two_way, limit, full),
+        Algorithm::Raita => raita_find(text, pattern, bad_char_shift_map, two_way, limit, full),
+        Algorithm::TunedBoyerMoore => {
+            tuned_find(text, pattern, bad_char_shift_map, two_way, limit, full)
+        },
+    }
+}
+
+/// Search backward by one of the classic algorithms. `Algorithm::MagicLen` and the bit-parallel algorithms are not handled here.
+pub(super) fn rfind<TT: ScanText, TP: BMByteSearchable>(
+    algorithm: Algorithm,
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    two_way_rev: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    match algorithm {
+        Algorithm::MagicLen | Algorithm::ShiftOr | Algorithm::Bndm => unreachable!(),
+        Algorithm::Horspool => {
+            horspool_rfind(text, pattern, bad_char_shift_map, two_way_rev, limit, full)
+        },
+        Algorithm::Sunday => {
+            sunday_rfind(text, pattern, bad_char_shift_map, two_way_rev, limit, full)
+        },
+        Algorithm::Raita => {
+            raita_rfind(text, pattern, bad_char_shift_map, two_way_rev, limit, full)
+        },
+        Algorithm::TunedBoyerMoore => {
+            tuned_rfind(text, pattern, bad_char_shift_map, two_way_rev, limit, full)
+        },
+    }
+}
+
+fn horspool_find<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    two_way: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+    let pattern_len_dec = pattern_len - 1;
+
+    let end_index = text_len - pattern_len;
+
+    let budget = budget(text_len, two_way);
+    let mut comparisons = 0;
+
+    let mut shift = 0;
+
+    let mut result = vec![];
+
+    loop {
+        if comparisons > budget {
+            two_way_find(two_way.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        let matched = pattern.iter().copied().enumerate().rev().all(|(i, pc)| {
+            comparisons += 1;
+
+            text.value_at(shift + i) == pc
+        });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        shift += if matched && !full {
+            pattern_len
+        } else {
+            bad_char_shift_map[text.value_at(shift + pattern_len_dec) as usize]
+        };
+
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+fn horspool_rfind<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    two_way_rev: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    let budget = budget(text_len, two_way_rev);
+    let mut comparisons = 0;
+
+    let mut shift = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    loop {
+        if comparisons > budget {
+            two_way_rfind(two_way_rev.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        let matched = pattern.iter().copied().enumerate().all(|(i, pc)| {
+            comparisons += 1;
+
+            text.value_at(shift + i) == pc
+        });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        let s = if matched && !full {
+            pattern_len
+        } else {
+            bad_char_shift_map[text.value_at(shift) as usize]
+        };
+
+        if shift < s {
+            break;
+        }
+
+        shift -= s;
+    }
+
+    result
+}
+
+fn sunday_find<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    two_way: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+    let pattern_len_dec = pattern_len - 1;
+
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    let end_index = text_len - pattern_len;
+
+    let budget = budget(text_len, two_way);
+    let mut comparisons = 0;
+
+    let mut shift = 0;
+
+    let mut result = vec![];
+
+    loop {
+        if comparisons > budget {
+            two_way_find(two_way.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        let matched = pattern.iter().copied().enumerate().all(|(i, pc)| {
+            comparisons += 1;
+
+            text.value_at(shift + i) == pc
+        });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        if matched && !full {
+            shift += pattern_len;
+        } else {
+            let p = shift + pattern_len;
+
+            if p == text_len {
+                break;
+            }
+
+            let c = text.value_at(p);
+
+            shift += if c == last_pattern_char { 1 } else { bad_char_shift_map[c as usize] + 1 };
+        }
+
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+fn sunday_rfind<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    two_way_rev: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    let first_pattern_char = pattern.value_at(0);
+
+    let budget = budget(text_len, two_way_rev);
+    let mut comparisons = 0;
+
+    let mut shift = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    loop {
+        if comparisons > budget {
+            two_way_rfind(two_way_rev.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        let matched = pattern.iter().copied().enumerate().rev().all(|(i, pc)| {
+            comparisons += 1;
+
+            text.value_at(shift + i) == pc
+        });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        let s = if matched && !full {
+            pattern_len
+        } else {
+            if shift == 0 {
+                break;
+            }
+
+            let c = text.value_at(shift - 1);
+
+            if c == first_pattern_char {
+                1
+            } else {
+                bad_char_shift_map[c as usize] + 1
+            }
+        };
+
+        if shift < s {
+            break;
+        }
+
+        shift -= s;
+    }
+
+    result
+}
+
+fn raita_find<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    two_way: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+    let pattern_len_dec = pattern_len - 1;
+    let pattern_len_half = pattern_len / 2;
+
+    let first_pattern_char = pattern.value_at(0);
+    let middle_pattern_char = pattern.value_at(pattern_len_half);
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    let end_index = text_len - pattern_len;
+
+    let budget = budget(text_len, two_way);
+    let mut comparisons = 0;
+
+    let mut shift = 0;
+
+    let mut result = vec![];
+
+    loop {
+        if comparisons > budget {
+            two_way_find(two_way.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        let last_char = text.value_at(shift + pattern_len_dec);
+
+        // the last, the first and the middle bytes are compared first
+        comparisons += 1;
+
+        let matched = last_char == last_pattern_char
+            && {
+                comparisons += 1;
+
+                text.value_at(shift) == first_pattern_char
+            }
+            && {
+                comparisons += 1;
+
+                text.value_at(shift + pattern_len_half) == middle_pattern_char
+            }
+            && (1..pattern_len_dec).all(|i| {
+                comparisons += 1;
+
+                text.value_at(shift + i) == pattern.value_at(i)
+            });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        shift +=
+            if matched && !full { pattern_len } else { bad_char_shift_map[last_char as usize] };
+
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+fn raita_rfind<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    two_way_rev: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+    let pattern_len_dec = pattern_len - 1;
+    let pattern_len_half = pattern_len / 2;
+
+    let first_pattern_char = pattern.value_at(0);
+    let middle_pattern_char = pattern.value_at(pattern_len_half);
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    let budget = budget(text_len, two_way_rev);
+    let mut comparisons = 0;
+
+    let mut shift = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    loop {
+        if comparisons > budget {
+            two_way_rfind(two_way_rev.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        let first_char = text.value_at(shift);
+
+        // the first, the last and the middle bytes are compared first
+        comparisons += 1;
+
+        let matched = first_char == first_pattern_char
+            && {
+                comparisons += 1;
+
+                text.value_at(shift + pattern_len_dec) == last_pattern_char
+            }
+            && {
+                comparisons += 1;
+
+                text.value_at(shift + pattern_len_half) == middle_pattern_char
+            }
+            && (1..pattern_len_dec).rev().all(|i| {
+                comparisons += 1;
+
+                text.value_at(shift + i) == pattern.value_at(i)
+            });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        let s =
+            if matched && !full { pattern_len } else { bad_char_shift_map[first_char as usize] };
+
+        if shift < s {
+            break;
+        }
+
+        shift -= s;
+    }
+
+    result
+}
+
+fn tuned_find<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMap,
+    two_way: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+    let pattern_len_dec = pattern_len - 1;
+
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    // the shift after the last byte of the window has matched
+    let match_shift = bad_char_shift_map[last_pattern_char as usize];
+
+    let end_index = text_len - pattern_len;
+
+    let budget = budget(text_len, two_way);
+    let mut comparisons = 0;
+
+    let mut shift = 0;
+
+    let mut result = vec![];
+
+    'outer: loop {
+        if comparisons > budget {
+            two_way_find(two_way.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        // the skip loop, which treats the last byte of the pattern as if its shift were 0
+        loop {
+            let c = text.value_at(shift + pattern_len_dec);
+
+            comparisons += 1;
+
+            if c == last_pattern_char {
+                break;
+            }
+
+            shift += bad_char_shift_map[c as usize];
+
+            if shift > end_index {
+                break 'outer;
+            }
+        }
+
+        let matched = (0..pattern_len_dec).all(|i| {
+            comparisons += 1;
+
+            text.value_at(shift + i) == pattern.value_at(i)
+        });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        shift += if matched && !full { pattern_len } else { match_shift };
+
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+fn tuned_rfind<TT: ScanText, TP: BMByteSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMByteBadCharShiftMapRev,
+    two_way_rev: Option<&TwoWay>,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    let first_pattern_char = pattern.value_at(0);
+
+    // the shift after the first byte of the window has matched
+    let match_shift = bad_char_shift_map[first_pattern_char as usize];
+
+    let budget = budget(text_len, two_way_rev);
+    let mut comparisons = 0;
+
+    let mut shift = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    'outer: loop {
+        if comparisons > budget {
+            two_way_rfind(two_way_rev.unwrap(), &text, &pattern, shift, limit, full, &mut result);
+
+            break;
+        }
+
+        // the skip loop, which treats the first byte of the pattern as if its shift were 0
+        loop {
+            let c = text.value_at(shift);
+
+            comparisons += 1;
+
+            if c == first_pattern_char {
+                break;
+            }
+
+            let s = bad_char_shift_map[c as usize];
+
+            if shift < s {
+                break 'outer;
+            }
+
+            shift -= s;
+        }
+
+        let matched = (1..pattern_len).rev().all(|i| {
+            comparisons += 1;
+
+            text.value_at(shift + i) == pattern.value_at(i)
+        });
+
+        if matched {
+            result.push(shift);
+
+            if result.len() == limit {
+                break;
+            }
+        }
+
+        let s = if matched && !full { pattern_len } else { match_shift };
+
+        if shift < s {
+            break;
+        }
+
+        shift -= s;
+    }
+
+    result
+}
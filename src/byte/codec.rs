@@ -0,0 +1,211 @@
+//! Framing byte streams on a multi-byte delimiter with `tokio-util`. The search is resumed from the last `pattern.len() - 1` bytes which have been searched, instead of the head of the buffer.
+
+use core::fmt::{self, Display, Formatter};
+use std::{error::Error, io};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use super::BMByte;
+
+/// A `tokio_util::codec::Decoder` and `tokio_util::codec::Encoder` which split a byte stream into frames separated by the pattern of a `BMByte` (the delimiter). The delimiters are not included in the decoded frames, and one is appended to every encoded frame.
+///
+/// ```
+/// use boyer_moore_magiclen::{BMByte, BMByteCodec};
+/// use bytes::BytesMut;
+/// use tokio_util::codec::Decoder;
+///
+/// let mut codec = BMByteCodec::new(BMByte::from("\r\n\r\n").unwrap());
+///
+/// let mut buf = BytesMut::from(&b"a\r\n\r\nb\r\n"[..]);
+///
+/// assert_eq!(&b"a"[..], codec.decode(&mut buf).unwrap().unwrap());
+/// assert_eq!(None, codec.decode(&mut buf).unwrap());
+///
+/// buf.extend_from_slice(b"\r\nc");
+///
+/// assert_eq!(&b"b"[..], codec.decode(&mut buf).unwrap().unwrap());
+/// assert_eq!(&b"c"[..], codec.decode_eof(&mut buf).unwrap().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct BMByteCodec {
+    bmb:           BMByte,
+    /// The index of the buffer to resume the search from (only used if `is_discarding` is `false`).
+    next_index:    usize,
+    max_length:    usize,
+    /// Whether the bytes are being discarded until the next delimiter because the frame is too long.
+    is_discarding: bool,
+}
+
+impl BMByteCodec {
+    /// Create a `BMByteCodec` instance without a maximum frame length.
+    #[inline]
+    pub fn new(bmb: BMByte) -> BMByteCodec {
+        BMByteCodec {
+            bmb,
+            next_index: 0,
+            max_length: usize::MAX,
+            is_discarding: false,
+        }
+    }
+
+    /// Create a `BMByteCodec` instance with a maximum frame length (not including the delimiter). If a frame (including the bytes left at the end of the stream) exceeds the length, `decode` (or `decode_eof`) returns a `BMByteCodecError::MaxFrameLengthExceeded` error, and then the bytes are discarded until the next delimiter. It is highly recommended for untrusted input, which may otherwise make the buffer grow unboundedly.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, BMByteCodec, BMByteCodecError};
+    /// use bytes::BytesMut;
+    /// use tokio_util::codec::Decoder;
+    ///
+    /// let mut codec =
+    ///     BMByteCodec::new_with_max_length(BMByte::from("\r\n").unwrap(), 3);
+    ///
+    /// let mut buf = BytesMut::from(&b"abcdef\r\nabc\r\n"[..]);
+    ///
+    /// assert!(matches!(
+    ///     codec.decode(&mut buf),
+    ///     Err(BMByteCodecError::MaxFrameLengthExceeded)
+    /// ));
+    /// assert_eq!(&b"abc"[..], codec.decode(&mut buf).unwrap().unwrap());
+    /// ```
+    #[inline]
+    pub fn new_with_max_length(bmb: BMByte, max_length: usize) -> BMByteCodec {
+        BMByteCodec {
+            max_length,
+            ..BMByteCodec::new(bmb)
+        }
+    }
+
+    /// Get the maximum frame length.
+    #[inline]
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl Decoder for BMByteCodec {
+    type Error = BMByteCodecError;
+    type Item = Bytes;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, BMByteCodecError> {
+        let pattern_len = self.bmb.pattern.len();
+
+        loop {
+            if self.is_discarding {
+                match self.bmb.find_first_in(&buf[..]) {
+                    Some(index) => {
+                        buf.advance(index + pattern_len);
+
+                        self.is_discarding = false;
+                    },
+                    None => {
+                        // keep the bytes which may be the head of a delimiter
+                        let keep = (pattern_len - 1).min(buf.len());
+
+                        buf.advance(buf.len() - keep);
+
+                        return Ok(None);
+                    },
+                }
+            } else {
+                // a frame and its delimiter must end before this index
+                let read_to = self.max_length.saturating_add(pattern_len).min(buf.len());
+
+                let next_index = self.next_index.min(read_to);
+
+                match self.bmb.find_first_in(&buf[next_index..read_to]) {
+                    Some(index) => {
+                        let index = next_index + index;
+
+                        self.next_index = 0;
+
+                        let mut frame = buf.split_to(index + pattern_len);
+
+                        frame.truncate(index);
+
+                        return Ok(Some(frame.freeze()));
+                    },
+                    None if buf.len() >= self.max_length.saturating_add(pattern_len) => {
+                        self.next_index = 0;
+                        self.is_discarding = true;
+
+                        return Err(BMByteCodecError::MaxFrameLengthExceeded);
+                    },
+                    None => {
+                        self.next_index = read_to.saturating_sub(pattern_len - 1);
+
+                        return Ok(None);
+                    },
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Bytes>, BMByteCodecError> {
+        Ok(match self.decode(buf)? {
+            Some(frame) => Some(frame),
+            None => {
+                self.next_index = 0;
+
+                if self.is_discarding {
+                    self.is_discarding = false;
+
+                    buf.clear();
+
+                    None
+                } else if buf.is_empty() {
+                    None
+                } else if buf.len() > self.max_length {
+                    buf.clear();
+
+                    return Err(BMByteCodecError::MaxFrameLengthExceeded);
+                } else {
+                    Some(buf.split().freeze())
+                }
+            },
+        })
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for BMByteCodec {
+    type Error = BMByteCodecError;
+
+    fn encode(&mut self, frame: T, buf: &mut BytesMut) -> Result<(), BMByteCodecError> {
+        let frame = frame.as_ref();
+
+        let delimiter = self.bmb.pattern();
+
+        buf.reserve(frame.len() + delimiter.len());
+        buf.put(frame);
+        buf.put(delimiter);
+
+        Ok(())
+    }
+}
+
+/// Errors of `BMByteCodec`.
+#[derive(Debug)]
+pub enum BMByteCodecError {
+    /// The maximum frame length was exceeded.
+    MaxFrameLengthExceeded,
+    /// An I/O error occurred.
+    Io(io::Error),
+}
+
+impl Display for BMByteCodecError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BMByteCodecError::MaxFrameLengthExceeded => f.write_str("max frame length exceeded"),
+            BMByteCodecError::Io(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for BMByteCodecError {}
+
+impl From<io::Error> for BMByteCodecError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        BMByteCodecError::Io(err)
+    }
+}
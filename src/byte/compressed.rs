@@ -0,0 +1,103 @@
+//! Searching compressed streams. The compression format is detected from the magic bytes at the head of the stream, and the stream is decompressed on the fly so that the positions are counted in the decompressed bytes.
+
+use std::{
+    fs::File,
+    io::{self, Cursor, ErrorKind, Read},
+    path::Path,
+};
+
+use super::{BMByte, StreamSearcher};
+
+/// The magic bytes of a gzip member.
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+
+/// The magic bytes of a zstd frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Wrap a reader with a decompressor chosen by the magic bytes at the head of the stream. Gzip (with the `flate2` feature, including multiple members) and zstd (with the `zstd` feature) are supported. If the stream is not compressed in a known format, it is read as it is. If the stream is compressed in a format whose feature is not enabled, an `Unsupported` error is returned.
+///
+/// ```
+/// use std::io::Read;
+///
+/// use boyer_moore_magiclen::byte::decompressing_reader;
+///
+/// let mut reader = decompressing_reader("not compressed".as_bytes()).unwrap();
+///
+/// let mut s = String::new();
+///
+/// reader.read_to_string(&mut s).unwrap();
+///
+/// assert_eq!("not compressed", s);
+/// ```
+pub fn decompressing_reader<'a, R: Read + 'a>(mut reader: R) -> io::Result<Box<dyn Read + 'a>> {
+    let mut magic = [0; 4];
+    let mut magic_len = 0;
+
+    while magic_len < magic.len() {
+        match reader.read(&mut magic[magic_len..]) {
+            Ok(0) => break,
+            Ok(c) => magic_len += c,
+            Err(ref err) if err.kind() == ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+
+    let magic = &magic[..magic_len];
+
+    // put the magic bytes back
+    let reader = Cursor::new(magic.to_vec()).chain(reader);
+
+    if magic.starts_with(&GZIP_MAGIC) {
+        #[cfg(feature = "flate2")]
+        {
+            Ok(Box::new(flate2::read::MultiGzDecoder::new(reader)))
+        }
+
+        #[cfg(not(feature = "flate2"))]
+        {
+            Err(io::Error::new(ErrorKind::Unsupported, "the `flate2` feature is needed for gzip"))
+        }
+    } else if magic == ZSTD_MAGIC {
+        #[cfg(feature = "zstd")]
+        {
+            Ok(Box::new(zstd::stream::read::Decoder::new(reader)?))
+        }
+
+        #[cfg(not(feature = "zstd"))]
+        {
+            Err(io::Error::new(ErrorKind::Unsupported, "the `zstd` feature is needed for zstd"))
+        }
+    } else {
+        Ok(Box::new(reader))
+    }
+}
+
+impl BMByte {
+    /// Find and return the positions of matched sub-sequences in a file which may be compressed but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found. The compression format is detected by `decompressing_reader`, and the positions are counted in the decompressed bytes.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let path = std::env::temp_dir()
+    ///     .join("boyer_moore_magiclen_find_in_compressed_file.txt");
+    ///
+    /// std::fs::write(&path, "coocoocoocoo").unwrap();
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 7], bmb.find_in_compressed_file(&path, 0).unwrap());
+    ///
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn find_in_compressed_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        limit: usize,
+    ) -> io::Result<Vec<u64>> {
+        let reader = decompressing_reader(File::open(path)?)?;
+
+        let limit = if limit == 0 { usize::MAX } else { limit };
+
+        StreamSearcher::new(self, reader).take(limit).collect()
+    }
+}
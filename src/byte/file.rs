@@ -0,0 +1,149 @@
+//! Searching files by mapping them into memory with `memmap2`. The files which cannot be mapped (e.g. pipes, special files, or files larger than the address space) are searched by `StreamSearcher` instead, or by `ReverseStreamSearcher` if a regular file is searched backward.
+
+use std::{fs::File, io, path::Path};
+
+use memmap2::Mmap;
+
+use super::{BMByte, ReverseStreamSearcher, StreamSearcher};
+
+/// Whether a file is a non-empty regular file, which can be mapped into memory or read from its tail. The empty files are excluded because some special files (e.g. the files in `/proc`) report a length of `0` but still have contents.
+fn is_regular(file: &File) -> bool {
+    file.metadata().is_ok_and(|metadata| metadata.is_file() && metadata.len() > 0)
+}
+
+/// Map a file into memory. If the file is not a non-empty regular file or it cannot be mapped, `None` is returned.
+fn map(file: &File) -> Option<Mmap> {
+    if !is_regular(file) {
+        return None;
+    }
+
+    // SAFETY: the file may be modified by other processes while it is mapped, in which case the results are unspecified but the memory is still valid to read
+    unsafe { Mmap::map(file) }.ok()
+}
+
+impl BMByte {
+    /// Find and return the positions of matched sub-sequences in a file but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found. The file is mapped into memory if possible, otherwise it is read as a stream.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let path =
+    ///     std::env::temp_dir().join("boyer_moore_magiclen_find_in_file.txt");
+    ///
+    /// std::fs::write(&path, "coocoocoocoo").unwrap();
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 7], bmb.find_in_file(&path, 0).unwrap());
+    /// assert_eq!(vec![1], bmb.find_in_file(&path, 1).unwrap());
+    ///
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn find_in_file<P: AsRef<Path>>(&self, path: P, limit: usize) -> io::Result<Vec<u64>> {
+        let file = File::open(path)?;
+
+        match map(&file) {
+            Some(mmap) => {
+                Ok(self.find_in(&mmap[..], limit).into_iter().map(|i| i as u64).collect())
+            },
+            None => {
+                let limit = if limit == 0 { usize::MAX } else { limit };
+
+                StreamSearcher::new(self, file).take(limit).collect()
+            },
+        }
+    }
+
+    /// Find and return the positions of matched sub-sequences in a file but not including the overlap from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found. The file is mapped into memory if possible, otherwise it is read as a stream from its tail if it is a regular file, or from its head if it is not seekable (e.g. a pipe).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let path =
+    ///     std::env::temp_dir().join("boyer_moore_magiclen_rfind_in_file.txt");
+    ///
+    /// std::fs::write(&path, "coocoocoocoo").unwrap();
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 1], bmb.rfind_in_file(&path, 0).unwrap());
+    /// assert_eq!(vec![7], bmb.rfind_in_file(&path, 1).unwrap());
+    ///
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn rfind_in_file<P: AsRef<Path>>(&self, path: P, limit: usize) -> io::Result<Vec<u64>> {
+        let file = File::open(path)?;
+
+        match map(&file) {
+            Some(mmap) => {
+                Ok(self.rfind_in(&mmap[..], limit).into_iter().map(|i| i as u64).collect())
+            },
+            None if is_regular(&file) => {
+                let limit = if limit == 0 { usize::MAX } else { limit };
+
+                ReverseStreamSearcher::new(self, file).take(limit).collect()
+            },
+            None => {
+                // the stream is not seekable, so find all the overlapping matches from the head and then pick the non-overlapping ones from the tail
+                let full_result =
+                    StreamSearcher::new(self, file).full(true).collect::<io::Result<Vec<u64>>>()?;
+
+                let pattern_len = self.pattern.len() as u64;
+
+                let mut result = Vec::new();
+
+                // the matches after this position overlap the last accepted match
+                let mut next_free = u64::MAX;
+
+                for &i in full_result.iter().rev() {
+                    if i + pattern_len <= next_free {
+                        result.push(i);
+
+                        if result.len() == limit {
+                            break;
+                        }
+
+                        next_free = i;
+                    }
+                }
+
+                Ok(result)
+            },
+        }
+    }
+
+    /// Count the matched sub-sequences in a file but not including the overlap. The file is mapped into memory if possible, otherwise it is read as a stream.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let path =
+    ///     std::env::temp_dir().join("boyer_moore_magiclen_count_in_file.txt");
+    ///
+    /// std::fs::write(&path, "coocoocoocoo").unwrap();
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(2, bmb.count_in_file(&path).unwrap());
+    ///
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn count_in_file<P: AsRef<Path>>(&self, path: P) -> io::Result<u64> {
+        let file = File::open(path)?;
+
+        match map(&file) {
+            Some(mmap) => Ok(self.find_all_in(&mmap[..]).len() as u64),
+            None => {
+                let mut count = 0;
+
+                for r in StreamSearcher::new(self, file) {
+                    r?;
+
+                    count += 1;
+                }
+
+                Ok(count)
+            },
+        }
+    }
+}
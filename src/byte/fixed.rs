@@ -0,0 +1,319 @@
+use alloc::vec::Vec;
+
+use super::BMByteSearchable;
+
+/// Using Boyer-Moore-MagicLen to search a byte sub-sequence whose length `N` is known at compile time (from 1 to 32 bytes). The pattern and the shift maps are held in arrays instead of the heap, and the verification of every window is unrolled. It gives the same results as `BMByte`.
+///
+/// ```compile_fail
+/// use boyer_moore_magiclen::BMByteN;
+///
+/// let bmb = BMByteN::<33>::from("oocoo");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BMByteN<const N: usize> {
+    bad_char_shift_map:     [u8; 256],
+    bad_char_shift_map_rev: [u8; 256],
+    pattern:                [u8; N],
+}
+
+impl<const N: usize> BMByteN<N> {
+    const VALID_N: () = assert!(N > 0 && N <= 32, "N must be from 1 to 32");
+
+    /// Create a `BMByteN` instance from a pattern (the needle). If the length of the pattern is not `N`, `None` is returned.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert!(BMByteN::<4>::from("oocoo").is_none());
+    /// ```
+    pub fn from<T: BMByteSearchable>(pattern: T) -> Option<BMByteN<N>> {
+        // fail to compile if `N` is out of range
+        let () = Self::VALID_N;
+
+        if pattern.len() != N {
+            return None;
+        }
+
+        let mut array = [0; N];
+
+        for (i, &c) in pattern.iter().enumerate() {
+            array[i] = c;
+        }
+
+        let mut bad_char_shift_map = [N as u8; 256];
+        let mut bad_char_shift_map_rev = [N as u8; 256];
+
+        for (i, &c) in array.iter().enumerate().take(N - 1) {
+            bad_char_shift_map[c as usize] = (N - 1 - i) as u8;
+        }
+
+        for (i, &c) in array.iter().enumerate().skip(1).rev() {
+            bad_char_shift_map_rev[c as usize] = i as u8;
+        }
+
+        Some(BMByteN {
+            bad_char_shift_map,
+            bad_char_shift_map_rev,
+            pattern: array,
+        })
+    }
+
+    /// Get the pattern.
+    #[inline]
+    pub fn pattern(&self) -> &[u8; N] {
+        &self.pattern
+    }
+
+    /// Whether the pattern is at `start` of the text. `N` is a constant, so the comparisons can be unrolled.
+    #[inline]
+    fn is_match_at<T: BMByteSearchable>(&self, text: &T, start: usize) -> bool {
+        self.pattern.iter().enumerate().all(|(i, &c)| text.value_at(start + i) == c)
+    }
+
+    fn find_forward<T: BMByteSearchable>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        let text_len = text.len();
+
+        let mut result = vec![];
+
+        if text_len < N {
+            return result;
+        }
+
+        let last_pattern_char = self.pattern[N - 1];
+
+        let end_index = text_len - N;
+
+        // `shift + N` must be in the text
+        let next_shift = |shift: usize| {
+            (self.bad_char_shift_map[text.value_at(shift + N - 1) as usize] as usize).max({
+                let c = text.value_at(shift + N);
+
+                if c == last_pattern_char {
+                    1
+                } else {
+                    self.bad_char_shift_map[c as usize] as usize + 1
+                }
+            })
+        };
+
+        let mut shift = 0;
+
+        loop {
+            if text.value_at(shift + N - 1) == last_pattern_char && self.is_match_at(&text, shift) {
+                result.push(shift);
+
+                if shift == end_index || result.len() == limit {
+                    break;
+                }
+
+                shift += if full { next_shift(shift) } else { N };
+            } else {
+                if shift == end_index {
+                    break;
+                }
+
+                shift += next_shift(shift);
+            }
+
+            if shift > end_index {
+                break;
+            }
+        }
+
+        result
+    }
+
+    fn find_backward<T: BMByteSearchable>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        let text_len = text.len();
+
+        let mut result = vec![];
+
+        if text_len < N {
+            return result;
+        }
+
+        let first_pattern_char = self.pattern[0];
+
+        // `start` must not be `0`
+        let next_shift = |start: usize| {
+            (self.bad_char_shift_map_rev[text.value_at(start) as usize] as usize).max({
+                let c = text.value_at(start - 1);
+
+                if c == first_pattern_char {
+                    1
+                } else {
+                    self.bad_char_shift_map_rev[c as usize] as usize + 1
+                }
+            })
+        };
+
+        let mut start = text_len - N;
+
+        loop {
+            let s = if text.value_at(start) == first_pattern_char && self.is_match_at(&text, start)
+            {
+                result.push(start);
+
+                if start == 0 || result.len() == limit {
+                    break;
+                }
+
+                if full {
+                    next_shift(start)
+                } else {
+                    N
+                }
+            } else {
+                if start == 0 {
+                    break;
+                }
+
+                next_shift(start)
+            };
+
+            if start < s {
+                break;
+            }
+
+            start -= s;
+        }
+
+        result
+    }
+}
+
+impl<const N: usize> BMByteN<N> {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 4, 7], bmb.find_full_all_in("coocoocoocoo"));
+    /// ```
+    pub fn find_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_forward(text, 0, true)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack). If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 4], bmb.find_full_in("coocoocoocoo", 2));
+    /// ```
+    pub fn find_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_forward(text, limit, true)
+    }
+
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
+    /// ```
+    pub fn find_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_forward(text, 0, false)
+    }
+
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(Some(1), bmb.find_first_in("coocoocoocoo"));
+    /// ```
+    pub fn find_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
+        self.find_forward(text, 1, false).first().copied()
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1], bmb.find_in("coocoocoocoo", 1));
+    /// ```
+    pub fn find_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_forward(text, limit, false)
+    }
+}
+
+impl<const N: usize> BMByteN<N> {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 4, 1], bmb.rfind_full_all_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_full_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_backward(text, 0, true)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 4], bmb.rfind_full_in("coocoocoocoo", 2));
+    /// ```
+    pub fn rfind_full_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_backward(text, limit, true)
+    }
+
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7, 1], bmb.rfind_all_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<usize> {
+        self.find_backward(text, 0, false)
+    }
+
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(Some(7), bmb.rfind_first_in("coocoocoocoo"));
+    /// ```
+    pub fn rfind_first_in<T: BMByteSearchable>(&self, text: T) -> Option<usize> {
+        self.find_backward(text, 1, false).first().copied()
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteN;
+    ///
+    /// let bmb = BMByteN::<5>::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![7], bmb.rfind_in("coocoocoocoo", 1));
+    /// ```
+    pub fn rfind_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        self.find_backward(text, limit, false)
+    }
+}
@@ -0,0 +1,208 @@
+//! Searching the bytes pushed piece by piece. Instead of keeping the last bytes of a piece, only the length of the longest suffix of the consumed bytes which is a prefix of the pattern (the state of Knuth-Morris-Pratt) is kept, so the consumed bytes are never scanned again. The matches straddling two pieces are found by running Knuth-Morris-Pratt on the first `pattern.len() - 1` bytes of the new piece, and the rest of the piece is searched by `BMByte`. The state for the next piece is then computed from the last `pattern.len() - 1` bytes, skipping the bytes which Knuth-Morris-Pratt has already read, so no byte is fed to it twice.
+
+use alloc::vec::Vec;
+
+use super::BMByte;
+use crate::good_suffix;
+
+/// Search a `BMByte` pattern in the bytes pushed piece by piece (e.g. the bytes received from a socket). The positions are counted from the first byte fed after creating or finishing the matcher.
+///
+/// ```
+/// use boyer_moore_magiclen::{BMByte, IncrementalMatcher};
+///
+/// let bmb = BMByte::from("\r\n\r\n").unwrap();
+///
+/// let mut matcher = IncrementalMatcher::new(&bmb);
+///
+/// assert_eq!(None, matcher.feed(b"GET / HTTP/1.1\r\nHost: a\r\n\r").next());
+/// assert_eq!(Some(23), matcher.feed(b"\nbody").next());
+/// assert_eq!(31, matcher.finish());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IncrementalMatcher<'a> {
+    bmb:       &'a BMByte,
+    borders:   Vec<usize>,
+    full:      bool,
+    /// The length of the longest suffix of the consumed bytes which is a proper prefix of the pattern.
+    state:     usize,
+    /// The number of the consumed bytes.
+    position:  u64,
+    /// The matches before this position overlap the last found match (only used if `full` is `false`).
+    next_free: u64,
+}
+
+impl<'a> IncrementalMatcher<'a> {
+    /// Create an `IncrementalMatcher` instance to search a `BMByte` pattern. The overlapping matches are not included by default.
+    #[inline]
+    pub fn new(bmb: &'a BMByte) -> IncrementalMatcher<'a> {
+        let pattern = &bmb.pattern;
+
+        IncrementalMatcher {
+            bmb,
+            borders: good_suffix::borders(pattern.len(), |i| pattern[i]),
+            full: false,
+            state: 0,
+            position: 0,
+            next_free: 0,
+        }
+    }
+
+    /// Set whether to include the overlapping matches, like the `find_full_all_in` method instead of the `find_all_in` method.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, IncrementalMatcher};
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// let mut matcher = IncrementalMatcher::new(&bmb).full(true);
+    ///
+    /// assert_eq!(vec![1], matcher.feed(b"coocoo").collect::<Vec<u64>>());
+    /// assert_eq!(vec![4, 7], matcher.feed(b"coocoo").collect::<Vec<u64>>());
+    /// ```
+    #[inline]
+    pub fn full(mut self, full: bool) -> Self {
+        self.full = full;
+
+        self
+    }
+
+    /// Get the number of the bytes which have been fed.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Get the length of the partial match at the end of the bytes which have been fed, i.e. the length of the longest suffix of them which is a prefix of the pattern.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, IncrementalMatcher};
+    ///
+    /// let bmb = BMByte::from("\r\n\r\n").unwrap();
+    ///
+    /// let mut matcher = IncrementalMatcher::new(&bmb);
+    ///
+    /// matcher.feed(b"Host: a\r\n\r");
+    ///
+    /// assert_eq!(3, matcher.partial_len());
+    /// ```
+    #[inline]
+    pub fn partial_len(&self) -> usize {
+        self.state
+    }
+
+    /// Consume the next piece of bytes, and return the positions of the matched sub-sequences which end in this piece.
+    pub fn feed(&mut self, bytes: &[u8]) -> impl Iterator<Item = u64> {
+        let pattern = &self.bmb.pattern;
+        let pattern_len = pattern.len();
+        let pattern_len_dec = pattern_len - 1;
+
+        let bytes_len = bytes.len();
+
+        let mut result = Vec::new();
+
+        // the matches which start in the consumed bytes end in the first `pattern_len - 1` bytes of this piece
+        let head_len = pattern_len_dec.min(bytes_len);
+
+        let mut state = self.state;
+
+        // the number of the bytes of this piece which `state` covers
+        let mut scanned = 0;
+
+        while scanned < head_len {
+            if state == 0 && head_len < bytes_len {
+                // no more matches can start in the consumed bytes, and the state will be computed from the last bytes
+                break;
+            }
+
+            state = self.step(state, bytes[scanned]);
+            scanned += 1;
+
+            if state == pattern_len {
+                let index = self.position + scanned as u64 - pattern_len as u64;
+
+                if self.full || index >= self.next_free {
+                    result.push(index);
+
+                    self.next_free = index + pattern_len as u64;
+                }
+
+                state = self.borders[pattern_len_dec];
+            }
+        }
+
+        if self.full {
+            result.extend(
+                self.bmb.find_full_all_in(bytes).into_iter().map(|i| self.position + i as u64),
+            );
+        } else {
+            let start = (self.next_free.saturating_sub(self.position) as usize).min(bytes_len);
+
+            let start_position = self.position + start as u64;
+
+            result.extend(
+                self.bmb
+                    .find_all_in(&bytes[start..])
+                    .into_iter()
+                    .map(|i| start_position + i as u64),
+            );
+
+            if let Some(&last) = result.last() {
+                self.next_free = last + pattern_len as u64;
+            }
+        }
+
+        if scanned < bytes_len {
+            // the partial match at the end is shorter than the pattern, so only the last `pattern_len - 1` bytes are needed, and the bytes which `state` already covers are not fed again
+            let tail_start = bytes_len - pattern_len_dec;
+
+            if tail_start > scanned {
+                state = 0;
+                scanned = tail_start;
+            }
+
+            for &c in bytes[scanned..].iter() {
+                state = self.step(state, c);
+
+                if state == pattern_len {
+                    // this match has been found by `BMByte`
+                    state = self.borders[pattern_len_dec];
+                }
+            }
+        }
+
+        self.state = state;
+        self.position += bytes_len as u64;
+
+        result.into_iter()
+    }
+
+    /// End the bytes, and reset this matcher so that it can be used for other bytes. Return the number of the bytes which have been fed. The partial match at the end, if any, is dropped.
+    #[inline]
+    pub fn finish(&mut self) -> u64 {
+        let position = self.position;
+
+        self.state = 0;
+        self.position = 0;
+        self.next_free = 0;
+
+        position
+    }
+
+    /// Advance the state of Knuth-Morris-Pratt by a byte. `state` must be smaller than the length of the pattern.
+    #[inline]
+    fn step(&self, mut state: usize, c: u8) -> usize {
+        let pattern = &self.bmb.pattern;
+
+        let c = self.bmb.fold(c);
+
+        while state > 0 && c != pattern[state] {
+            state = self.borders[state - 1];
+        }
+
+        if c == pattern[state] {
+            state + 1
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Parser combinators for `nom` which take the input until a `BMByte` pattern, instead of the naive search of `nom::bytes::complete::take_until` and `nom::bytes::streaming::take_until`. The input can be any `nom::Input` which implements `nom::AsBytes`, such as `&[u8]` and `&str`. For `&str`, the input is never split in the middle of a character: if the pattern is not valid UTF-8 and its occurrence starts in the middle of a character, a `nom::error::ErrorKind::TakeUntil` error is returned.
+
+use nom::{AsBytes, Input};
+
+/// Parsers for complete inputs. If the pattern is not found, an error is returned.
+pub mod complete;
+/// Parsers for streaming inputs. If the pattern is not found, `nom::Err::Incomplete` is returned.
+pub mod streaming;
+
+/// Whether the input can be split at the byte index `index`, i.e. `index` is not in the middle of an element of the input (e.g. a character of `&str`).
+fn is_split_index<I: Input + AsBytes>(input: &I, index: usize) -> bool {
+    match input.as_bytes().get(index) {
+        // only a UTF-8 continuation byte can be in the middle of an element
+        Some(&c) if c & 0xC0 == 0x80 => {
+            input.iter_indices().map(|(i, _)| i).take_while(|&i| i <= index).last() == Some(index)
+        },
+        _ => true,
+    }
+}
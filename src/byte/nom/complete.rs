@@ -0,0 +1,61 @@
+use nom::{
+    error::{ErrorKind, ParseError},
+    AsBytes, Err, IResult, Input,
+};
+
+use super::is_split_index;
+use crate::BMByte;
+
+/// Return the input before the first occurrence of the pattern of `bmb`. The pattern is not consumed. If the pattern is not found (or its occurrence starts in the middle of a character), a `nom::error::ErrorKind::TakeUntil` error is returned.
+///
+/// ```
+/// use boyer_moore_magiclen::{byte::nom::complete::take_until_bm, BMByte};
+/// use nom::IResult;
+///
+/// let bmb = BMByte::from("eof").unwrap();
+///
+/// let result: IResult<&str, &str> = take_until_bm(&bmb)("hello, worldeof");
+/// assert_eq!(Ok(("eof", "hello, world")), result);
+///
+/// let result: IResult<&str, &str> = take_until_bm(&bmb)("hello, world");
+/// assert!(result.is_err());
+/// ```
+pub fn take_until_bm<I: Input + AsBytes, E: ParseError<I>>(
+    bmb: &BMByte,
+) -> impl Fn(I) -> IResult<I, I, E> + '_ {
+    move |input: I| {
+        let index = bmb.find_first_in(input.as_bytes());
+
+        match index {
+            Some(index) if is_split_index(&input, index) => Ok(input.take_split(index)),
+            _ => Err(Err::Error(E::from_error_kind(input, ErrorKind::TakeUntil))),
+        }
+    }
+}
+
+/// Return the input before the last occurrence of the pattern of `bmb`. The pattern is not consumed. If the pattern is not found (or its occurrence starts in the middle of a character), a `nom::error::ErrorKind::TakeUntil` error is returned.
+///
+/// ```
+/// use boyer_moore_magiclen::{
+///     byte::nom::complete::take_until_bm_rev, BMByte,
+/// };
+/// use nom::IResult;
+///
+/// let bmb = BMByte::from("eof").unwrap();
+///
+/// let result: IResult<&[u8], &[u8]> =
+///     take_until_bm_rev(&bmb)(&b"a eof b eof c"[..]);
+/// assert_eq!(Ok((&b"eof c"[..], &b"a eof b "[..])), result);
+/// ```
+pub fn take_until_bm_rev<I: Input + AsBytes, E: ParseError<I>>(
+    bmb: &BMByte,
+) -> impl Fn(I) -> IResult<I, I, E> + '_ {
+    move |input: I| {
+        let index = bmb.rfind_first_in(input.as_bytes());
+
+        match index {
+            Some(index) if is_split_index(&input, index) => Ok(input.take_split(index)),
+            _ => Err(Err::Error(E::from_error_kind(input, ErrorKind::TakeUntil))),
+        }
+    }
+}
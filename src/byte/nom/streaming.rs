@@ -0,0 +1,94 @@
+use nom::{
+    error::{ErrorKind, ParseError},
+    AsBytes, Err, IResult, Input, Needed,
+};
+
+use super::is_split_index;
+use crate::{good_suffix, BMByte};
+
+/// Compute the minimum number of bytes which have to be appended to `bytes` for the pattern of `bmb` to occur, i.e. the length of the pattern minus the length of the longest suffix of `bytes` which is a proper prefix of the pattern.
+fn needed(bmb: &BMByte, bytes: &[u8]) -> Needed {
+    let pattern = &bmb.pattern;
+    let pattern_len = pattern.len();
+
+    let borders = good_suffix::borders(pattern_len, |i| pattern[i]);
+
+    // the partial match at the end is shorter than the pattern, so it is found by scanning the last `pattern_len - 1` bytes
+    let partial_len =
+        bytes[bytes.len().saturating_sub(pattern_len - 1)..].iter().fold(0, |mut state, &c| {
+            let c = bmb.fold(c);
+
+            while state > 0 && c != pattern[state] {
+                state = borders[state - 1];
+            }
+
+            if c == pattern[state] {
+                state + 1
+            } else {
+                0
+            }
+        });
+
+    Needed::new(pattern_len - partial_len)
+}
+
+/// Return the input before the first occurrence of the pattern of `bmb`. The pattern is not consumed. If the pattern is not found, `nom::Err::Incomplete` is returned with the minimum number of bytes needed for the pattern to occur. If its occurrence starts in the middle of a character, a `nom::error::ErrorKind::TakeUntil` error is returned.
+///
+/// ```
+/// use boyer_moore_magiclen::{byte::nom::streaming::take_until_bm, BMByte};
+/// use nom::{Err, IResult, Needed};
+///
+/// let bmb = BMByte::from("eof").unwrap();
+///
+/// let result: IResult<&[u8], &[u8]> =
+///     take_until_bm(&bmb)(&b"hello, worldeof"[..]);
+/// assert_eq!(Ok((&b"eof"[..], &b"hello, world"[..])), result);
+///
+/// let result: IResult<&[u8], &[u8]> =
+///     take_until_bm(&bmb)(&b"hello, worlde"[..]);
+/// assert_eq!(Err(Err::Incomplete(Needed::new(2))), result);
+/// ```
+pub fn take_until_bm<I: Input + AsBytes, E: ParseError<I>>(
+    bmb: &BMByte,
+) -> impl Fn(I) -> IResult<I, I, E> + '_ {
+    move |input: I| {
+        let bytes = input.as_bytes();
+
+        match bmb.find_first_in(bytes) {
+            Some(index) if is_split_index(&input, index) => Ok(input.take_split(index)),
+            Some(_) => Err(Err::Error(E::from_error_kind(input, ErrorKind::TakeUntil))),
+            None => Err(Err::Incomplete(needed(bmb, bytes))),
+        }
+    }
+}
+
+/// Return the input before the last occurrence of the pattern of `bmb` in the available input. The pattern is not consumed. Because more input may contain later occurrences, the result is only final if the rest of the input is known not to contain the pattern. If the pattern is not found, `nom::Err::Incomplete` is returned with the minimum number of bytes needed for the pattern to occur. If its occurrence starts in the middle of a character, a `nom::error::ErrorKind::TakeUntil` error is returned.
+///
+/// ```
+/// use boyer_moore_magiclen::{
+///     byte::nom::streaming::take_until_bm_rev, BMByte,
+/// };
+/// use nom::{Err, IResult, Needed};
+///
+/// let bmb = BMByte::from("eof").unwrap();
+///
+/// let result: IResult<&[u8], &[u8]> =
+///     take_until_bm_rev(&bmb)(&b"a eof b eof c"[..]);
+/// assert_eq!(Ok((&b"eof c"[..], &b"a eof b "[..])), result);
+///
+/// let result: IResult<&[u8], &[u8]> = take_until_bm_rev(&bmb)(&b"a"[..]);
+/// assert_eq!(Err(Err::Incomplete(Needed::new(3))), result);
+/// ```
+pub fn take_until_bm_rev<I: Input + AsBytes, E: ParseError<I>>(
+    bmb: &BMByte,
+) -> impl Fn(I) -> IResult<I, I, E> + '_ {
+    move |input: I| {
+        let bytes = input.as_bytes();
+
+        match bmb.rfind_first_in(bytes) {
+            Some(index) if is_split_index(&input, index) => Ok(input.take_split(index)),
+            Some(_) => Err(Err::Error(E::from_error_kind(input, ErrorKind::TakeUntil))),
+            None => Err(Err::Incomplete(needed(bmb, bytes))),
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Searching large texts in parallel with `rayon`. The text is split into chunks, and every chunk is extended by `pattern.len() - 1` bytes so that the matches crossing the boundaries are not missed. A match belongs to the chunk where it starts, so no match is found twice.
+
+use alloc::vec::Vec;
+
+use rayon::prelude::*;
+
+use super::{BMByte, BMByteSearchable, ScanText};
+
+/// The minimum length of a chunk. Smaller texts are not worth splitting.
+const MIN_CHUNK_LEN: usize = 64 * 1024;
+
+/// The number of chunks per thread, so that the threads can balance the work when the matches are not evenly distributed.
+const CHUNKS_PER_THREAD: usize = 4;
+
+/// A range of a text, which is read by `value_at` of the whole text.
+struct TextRange<'a, T: BMByteSearchable> {
+    text:  &'a T,
+    start: usize,
+    end:   usize,
+}
+
+impl<T: BMByteSearchable> ScanText for TextRange<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> u8 {
+        self.text.value_at(self.start + index)
+    }
+}
+
+impl BMByte {
+    /// Compute the length of the chunks. If the text should not be split, `None` is returned.
+    fn chunk_len(&self, text_len: usize) -> Option<usize> {
+        let chunk_count = rayon::current_num_threads() * CHUNKS_PER_THREAD;
+
+        let chunk_len = (text_len / chunk_count).max(MIN_CHUNK_LEN).max(self.pattern.len());
+
+        if chunk_len >= text_len {
+            None
+        } else {
+            Some(chunk_len)
+        }
+    }
+
+    /// Search every chunk in parallel. `f` is given the text from the start of the chunk and the end of the chunk (relative to the start), and it must return the positions (relative to the start) of the matches which start before the end.
+    fn par_search<
+        T: BMByteSearchable + Sync,
+        F: Fn(TextRange<'_, T>, usize) -> Vec<usize> + Sync,
+    >(
+        &self,
+        text: &T,
+        chunk_len: usize,
+        f: F,
+    ) -> Vec<(usize, Vec<usize>)> {
+        let text_len = text.len();
+        let pattern_len_dec = self.pattern.len() - 1;
+
+        (0..(text_len + chunk_len - 1) / chunk_len)
+            .into_par_iter()
+            .map(|i| {
+                let start = i * chunk_len;
+                let end = (start + chunk_len + pattern_len_dec).min(text_len);
+
+                (
+                    start,
+                    f(
+                        TextRange {
+                            text,
+                            start,
+                            end,
+                        },
+                        chunk_len,
+                    ),
+                )
+            })
+            .collect()
+    }
+
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) by multiple threads. The results are the same as the `find_full_all_in` method.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 4, 7], bmb.par_find_full_all_in("coocoocoocoo"));
+    /// ```
+    pub fn par_find_full_all_in<T: BMByteSearchable + Sync>(&self, text: T) -> Vec<usize> {
+        let chunk_len = match self.chunk_len(text.len()) {
+            Some(chunk_len) => chunk_len,
+            None => return self.find_full_all_in(text),
+        };
+
+        let results = self.par_search(&text, chunk_len, |chunk, end| {
+            let mut result = self.find_forward(chunk, 0, true);
+
+            result.retain(|&i| i < end);
+
+            result
+        });
+
+        let mut result = Vec::with_capacity(results.iter().map(|(_, r)| r.len()).sum());
+
+        for (start, r) in results {
+            result.extend(r.into_iter().map(|i| start + i));
+        }
+
+        result
+    }
+
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap by multiple threads. The results are the same as the `find_all_in` method.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(vec![1, 7], bmb.par_find_all_in("coocoocoocoo"));
+    /// ```
+    pub fn par_find_all_in<T: BMByteSearchable + Sync>(&self, text: T) -> Vec<usize> {
+        let chunk_len = match self.chunk_len(text.len()) {
+            Some(chunk_len) => chunk_len,
+            None => return self.find_all_in(text),
+        };
+
+        let results = self.par_search(&text, chunk_len, |chunk, end| {
+            let mut result = self.find_forward(chunk, 0, false);
+
+            result.retain(|&i| i < end);
+
+            result
+        });
+
+        let pattern_len = self.pattern.len();
+
+        let mut result = Vec::with_capacity(results.iter().map(|(_, r)| r.len()).sum());
+
+        // the matches before this position overlap the last accepted match
+        let mut next_free = 0;
+
+        for (start, r) in results {
+            match r.first() {
+                Some(&first) if start + first < next_free => {
+                    // the previous chunk's last match overlaps this chunk's first match, so search this chunk again from the end of that match
+                    let end = (start + chunk_len + pattern_len - 1).min(text.len());
+
+                    result.extend(
+                        self.find_forward(
+                            TextRange {
+                                text: &text,
+                                start: next_free,
+                                end,
+                            },
+                            0,
+                            false,
+                        )
+                        .into_iter()
+                        .map(|i| next_free + i)
+                        .take_while(|&i| i < start + chunk_len),
+                    );
+                },
+                _ => result.extend(r.into_iter().map(|i| start + i)),
+            }
+
+            if let Some(&last) = result.last() {
+                next_free = last + pattern_len;
+            }
+        }
+
+        result
+    }
+
+    /// Count the matched sub-sequences in any text (the haystack) but not including the overlap by multiple threads. The result is the same as the length of the result of the `find_all_in` method.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByte;
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// assert_eq!(2, bmb.par_count_in("coocoocoocoo"));
+    /// ```
+    #[inline]
+    pub fn par_count_in<T: BMByteSearchable + Sync>(&self, text: T) -> usize {
+        self.par_find_all_in(text).len()
+    }
+}
@@ -0,0 +1,132 @@
+//! Replacing a pattern in the bytes flowing through a `std::io::Write`. The matches are found by `IncrementalMatcher`, and only the partial match at the end of the written bytes is held back.
+
+use std::io::{Result, Write};
+
+use super::{BMByte, BMByteSearchable, IncrementalMatcher};
+
+/// A `std::io::Write` adapter which replaces every non-overlapping occurrence of a `BMByte` pattern with a replacement and writes the result to the inner writer. The bytes which may be the head of an occurrence are held back until the following bytes arrive. Calling `flush` or dropping the writer writes the held bytes as they are, so an occurrence straddling a `flush` call is not replaced.
+///
+/// ```
+/// use std::io::Write;
+///
+/// use boyer_moore_magiclen::{BMByte, ReplaceWriter};
+///
+/// let bmb = BMByte::from("secret").unwrap();
+///
+/// let mut writer = ReplaceWriter::new(&bmb, "******", Vec::new());
+///
+/// writer.write_all(b"password: sec").unwrap();
+/// writer.write_all(b"ret, token: secret").unwrap();
+///
+/// assert_eq!(
+///     b"password: ******, token: ******",
+///     writer.into_inner().unwrap().as_slice()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ReplaceWriter<'a, W: Write> {
+    matcher:     IncrementalMatcher<'a>,
+    pattern_len: usize,
+    replacement: Vec<u8>,
+    /// The bytes which have been written to this writer but not to the inner writer.
+    pending:     Vec<u8>,
+    inner:       Option<W>,
+}
+
+impl<'a, W: Write> ReplaceWriter<'a, W> {
+    /// Create a `ReplaceWriter` instance to replace the pattern of `bmb` with `replacement`.
+    pub fn new<T: BMByteSearchable>(
+        bmb: &'a BMByte,
+        replacement: T,
+        inner: W,
+    ) -> ReplaceWriter<'a, W> {
+        ReplaceWriter {
+            matcher:     IncrementalMatcher::new(bmb),
+            pattern_len: bmb.pattern.len(),
+            replacement: replacement.iter().copied().collect(),
+            pending:     Vec::new(),
+            inner:       Some(inner),
+        }
+    }
+
+    /// Get a reference to the inner writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().unwrap()
+    }
+
+    /// Write the held bytes, and then unwrap this `ReplaceWriter`, returning the inner writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.write_pending()?;
+
+        Ok(self.inner.take().unwrap())
+    }
+
+    /// Write the held bytes to the inner writer as they are, and reset the matcher.
+    fn write_pending(&mut self) -> Result<()> {
+        self.matcher.finish();
+
+        let inner = self.inner.as_mut().unwrap();
+
+        inner.write_all(&self.pending)?;
+
+        self.pending.clear();
+
+        Ok(())
+    }
+}
+
+impl<'a, W: Write> Write for ReplaceWriter<'a, W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        // the position of `pending[0]` counted by the matcher
+        let pending_position = self.matcher.position() - self.pending.len() as u64;
+
+        let mut data = core::mem::take(&mut self.pending);
+
+        data.extend_from_slice(buf);
+
+        let mut output = Vec::with_capacity(data.len());
+
+        // the bytes before this index have been handled
+        let mut handled = 0;
+
+        for position in self.matcher.feed(buf) {
+            let index = (position - pending_position) as usize;
+
+            output.extend_from_slice(&data[handled..index]);
+            output.extend_from_slice(&self.replacement);
+
+            handled = index + self.pattern_len;
+        }
+
+        // the bytes overlapping the last occurrence cannot be the head of another occurrence
+        let hold = self.matcher.partial_len().min(data.len() - handled);
+
+        let hold_index = data.len() - hold;
+
+        output.extend_from_slice(&data[handled..hold_index]);
+
+        data.drain(..hold_index);
+
+        self.pending = data;
+
+        self.inner.as_mut().unwrap().write_all(&output)?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.write_pending()?;
+
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<'a, W: Write> Drop for ReplaceWriter<'a, W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            // the errors cannot be reported here, so call `flush` or `into_inner` to handle them
+            let _ = self.write_pending();
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Searching any `std::io::Read + std::io::Seek` stream from its tail to its head. The stream is read backward in blocks of a fixed size, and the first `pattern.len() - 1` bytes of a block are carried to the previous block so that the matches straddling two blocks are not missed. A match always starts in the newly read block, so no match is found twice.
+
+use std::{
+    collections::VecDeque,
+    io::{Read, Seek, SeekFrom},
+};
+
+use super::{stream::DEFAULT_BUFFER_SIZE, BMByte};
+
+/// Search a `BMByte` pattern in any `std::io::Read + std::io::Seek` stream from its tail to its head, without reading the whole stream. It is an iterator over the absolute positions (from the head of the stream) of the matched sub-sequences. If an I/O error occurs, the error is yielded and the iteration ends.
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use boyer_moore_magiclen::{BMByte, ReverseStreamSearcher};
+///
+/// let bmb = BMByte::from("startxref").unwrap();
+///
+/// let reader = Cursor::new(b"startxref\n0\n%%EOF\nstartxref\n9\n%%EOF\n");
+///
+/// let mut searcher = ReverseStreamSearcher::new(&bmb, reader);
+///
+/// assert_eq!(18, searcher.next().unwrap().unwrap());
+/// ```
+#[derive(Debug)]
+pub struct ReverseStreamSearcher<'a, R: Read + Seek> {
+    bmb:         &'a BMByte,
+    reader:      R,
+    full:        bool,
+    buffer_size: usize,
+    bytes:       Vec<u8>,
+    /// The position of `bytes[0]` in the stream, or `None` if the length of the stream is not known yet.
+    offset:      Option<u64>,
+    /// The matches after this position overlap the last found match (only used if `full` is `false`).
+    next_free:   u64,
+    results:     VecDeque<u64>,
+    finished:    bool,
+}
+
+impl<'a, R: Read + Seek> ReverseStreamSearcher<'a, R> {
+    /// Create a `ReverseStreamSearcher` instance to search a `BMByte` pattern in a stream from its tail to its head. The overlapping matches are not included by default.
+    #[inline]
+    pub fn new(bmb: &'a BMByte, reader: R) -> ReverseStreamSearcher<'a, R> {
+        ReverseStreamSearcher {
+            bmb,
+            reader,
+            full: false,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            bytes: Vec::new(),
+            offset: None,
+            next_free: u64::MAX,
+            results: VecDeque::new(),
+            finished: false,
+        }
+    }
+
+    /// Set whether to include the overlapping matches, like the `rfind_full_all_in` method instead of the `rfind_all_in` method.
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use boyer_moore_magiclen::{BMByte, ReverseStreamSearcher};
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// let reader = Cursor::new("coocoocoocoo");
+    ///
+    /// let result: Vec<u64> = ReverseStreamSearcher::new(&bmb, reader)
+    ///     .full(true)
+    ///     .map(|r| r.unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![7, 4, 1], result);
+    /// ```
+    #[inline]
+    pub fn full(mut self, full: bool) -> Self {
+        self.full = full;
+
+        self
+    }
+
+    /// Set the number of bytes read from the stream for each block. The default size is 64 KiB. A size of `0` is treated as `1`.
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use boyer_moore_magiclen::{BMByte, ReverseStreamSearcher};
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// let reader = Cursor::new("coocoocoocoo");
+    ///
+    /// let result: Vec<u64> = ReverseStreamSearcher::new(&bmb, reader)
+    ///     .buffer_size(3)
+    ///     .map(|r| r.unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![7, 1], result);
+    /// ```
+    #[inline]
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+
+        self
+    }
+
+    /// Unwrap this `ReverseStreamSearcher`, returning the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read the block before the carried bytes. Return `false` if the head of the stream has been reached.
+    fn read_block(&mut self) -> std::io::Result<bool> {
+        let offset = match self.offset {
+            Some(offset) => offset,
+            None => self.reader.seek(SeekFrom::End(0))?,
+        };
+
+        self.offset = Some(offset);
+
+        if offset == 0 {
+            return Ok(false);
+        }
+
+        let block_len = (self.buffer_size as u64).min(offset) as usize;
+        let block_offset = offset - block_len as u64;
+
+        let keep = self.bytes.len();
+
+        self.bytes.resize(block_len + keep, 0);
+        self.bytes.copy_within(..keep, block_len);
+
+        self.reader.seek(SeekFrom::Start(block_offset))?;
+        self.reader.read_exact(&mut self.bytes[..block_len])?;
+
+        self.offset = Some(block_offset);
+
+        Ok(true)
+    }
+
+    /// Search the bytes, and then drop the bytes which can no longer be the tail of a match.
+    fn search(&mut self) {
+        let pattern_len = self.bmb.pattern.len();
+
+        let offset = self.offset.unwrap();
+
+        if self.full {
+            self.results.extend(
+                self.bmb
+                    .rfind_full_all_in(self.bytes.as_slice())
+                    .into_iter()
+                    .map(|i| offset + i as u64),
+            );
+        } else {
+            let end = (self.next_free.saturating_sub(offset).min(self.bytes.len() as u64)) as usize;
+
+            self.results.extend(
+                self.bmb.rfind_all_in(&self.bytes[..end]).into_iter().map(|i| offset + i as u64),
+            );
+
+            if let Some(&last) = self.results.back() {
+                self.next_free = self.next_free.min(last);
+            }
+        }
+
+        self.bytes.truncate(pattern_len - 1);
+    }
+}
+
+impl<'a, R: Read + Seek> Iterator for ReverseStreamSearcher<'a, R> {
+    type Item = std::io::Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(position) = self.results.pop_front() {
+                return Some(Ok(position));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            match self.read_block() {
+                Ok(true) => self.search(),
+                Ok(false) => self.finished = true,
+                Err(err) => {
+                    self.finished = true;
+
+                    return Some(Err(err));
+                },
+            }
+        }
+    }
+}
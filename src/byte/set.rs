@@ -0,0 +1,448 @@
+//! Searching many patterns at once by the Wu-Manber algorithm. The window is as long as the shortest pattern, and its last block (two bytes, or one byte if the shortest pattern has only one byte) is hashed to look up a bad character style shift shared by all patterns. If the shift is `0`, the patterns whose prefix of the window length ends with the same block are verified. Searching backward runs the same algorithm on the reversed text with the reversed patterns.
+
+use alloc::vec::Vec;
+
+use super::BMByteSearchable;
+
+/// The number of the buckets of the block hash.
+const HASH_SIZE: usize = 4096;
+
+/// How the matches of a `BMByteSet` are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MatchKind {
+    /// The matches do not overlap. The match which starts first is chosen, and among the matches starting at the same position, the pattern given first wins. This is the default kind.
+    #[default]
+    LeftmostFirst,
+    /// The matches do not overlap. The match which starts first is chosen, and among the matches starting at the same position, the longest pattern wins (or the pattern given first if they are equally long).
+    LeftmostLongest,
+    /// All the matches are reported, including the overlapping ones.
+    All,
+}
+
+/// The shift table and the buckets of the Wu-Manber algorithm for one direction.
+#[derive(Debug)]
+struct WuManber {
+    /// The patterns, reversed for searching backward.
+    patterns:      Vec<Vec<u8>>,
+    min_len:       usize,
+    block_len:     usize,
+    shift:         Vec<usize>,
+    /// The patterns of the `h`-th bucket are `bucket_ids[bucket_starts[h]..bucket_starts[h + 1]]`, in ascending order.
+    bucket_starts: Vec<usize>,
+    bucket_ids:    Vec<usize>,
+}
+
+impl WuManber {
+    fn new(patterns: Vec<Vec<u8>>) -> WuManber {
+        let min_len = patterns.iter().map(|p| p.len()).min().unwrap();
+        let block_len = if min_len >= 2 { 2 } else { 1 };
+
+        let mut shift = vec![min_len - block_len + 1; HASH_SIZE];
+        let mut bucket_starts = vec![0; HASH_SIZE + 1];
+
+        for pattern in patterns.iter() {
+            for i in block_len - 1..min_len {
+                let h = Self::hash(block_len, |k| pattern[k], i);
+
+                shift[h] = shift[h].min(min_len - 1 - i);
+            }
+
+            bucket_starts[Self::hash(block_len, |k| pattern[k], min_len - 1) + 1] += 1;
+        }
+
+        for h in 0..HASH_SIZE {
+            bucket_starts[h + 1] += bucket_starts[h];
+        }
+
+        let mut bucket_ids = vec![0; patterns.len()];
+        let mut next = bucket_starts.clone();
+
+        for (id, pattern) in patterns.iter().enumerate() {
+            let h = Self::hash(block_len, |k| pattern[k], min_len - 1);
+
+            bucket_ids[next[h]] = id;
+            next[h] += 1;
+        }
+
+        WuManber {
+            patterns,
+            min_len,
+            block_len,
+            shift,
+            bucket_starts,
+            bucket_ids,
+        }
+    }
+
+    /// Hash the block which ends at `end`.
+    #[inline]
+    fn hash(block_len: usize, at: impl Fn(usize) -> u8, end: usize) -> usize {
+        if block_len == 2 {
+            ((at(end - 1) as usize) << 4) ^ at(end) as usize
+        } else {
+            at(end) as usize
+        }
+    }
+
+    /// Find the matches in the text accessed by `at`. Return the IDs of the patterns and the start positions.
+    fn find(
+        &self,
+        text_len: usize,
+        at: impl Fn(usize) -> u8,
+        kind: MatchKind,
+        limit: usize,
+    ) -> Vec<(usize, usize)> {
+        let mut result = vec![];
+
+        let min_len = self.min_len;
+
+        // the end of the window
+        let mut end = min_len - 1;
+
+        while end < text_len {
+            let h = Self::hash(self.block_len, &at, end);
+
+            let shift = self.shift[h];
+
+            if shift > 0 {
+                end += shift;
+
+                continue;
+            }
+
+            let start = end + 1 - min_len;
+
+            // the ID and the length of the chosen pattern
+            let mut chosen: Option<(usize, usize)> = None;
+
+            for &id in self.bucket_ids[self.bucket_starts[h]..self.bucket_starts[h + 1]].iter() {
+                let pattern = &self.patterns[id];
+                let pattern_len = pattern.len();
+
+                if start + pattern_len > text_len
+                    || !pattern.iter().enumerate().all(|(i, &c)| at(start + i) == c)
+                {
+                    continue;
+                }
+
+                match kind {
+                    MatchKind::All => {
+                        result.push((id, start));
+
+                        if result.len() == limit {
+                            return result;
+                        }
+                    },
+                    MatchKind::LeftmostFirst => {
+                        chosen = Some((id, pattern_len));
+
+                        break;
+                    },
+                    MatchKind::LeftmostLongest => {
+                        if chosen.map_or(true, |(_, len)| pattern_len > len) {
+                            chosen = Some((id, pattern_len));
+                        }
+                    },
+                }
+            }
+
+            match chosen {
+                Some((id, pattern_len)) => {
+                    result.push((id, start));
+
+                    if result.len() == limit {
+                        break;
+                    }
+
+                    end = start + pattern_len + min_len - 1;
+                },
+                None => end += 1,
+            }
+        }
+
+        result
+    }
+}
+
+/// Using the Wu-Manber algorithm (built on the same bad character idea as Boyer-Moore-MagicLen) to search many byte sub-sequences at once. The matches are reported as the IDs of the patterns (their indices in the given order) and the positions.
+#[derive(Debug)]
+pub struct BMByteSet {
+    forward:    WuManber,
+    reverse:    WuManber,
+    match_kind: MatchKind,
+}
+
+/// A builder to create a `BMByteSet` instance with options.
+#[derive(Debug, Clone)]
+pub struct BMByteSetBuilder {
+    patterns:   Vec<Vec<u8>>,
+    match_kind: MatchKind,
+}
+
+impl BMByteSetBuilder {
+    /// Set how the matches are reported. The default kind is `MatchKind::LeftmostFirst`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByteSet, MatchKind};
+    ///
+    /// let set = BMByteSet::builder(["ab", "abcd"])
+    ///     .match_kind(MatchKind::LeftmostLongest)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(vec![(1, 0)], set.find_all_in("abcd"));
+    /// ```
+    #[inline]
+    pub fn match_kind(mut self, match_kind: MatchKind) -> Self {
+        self.match_kind = match_kind;
+
+        self
+    }
+
+    /// Create a `BMByteSet` instance. If there is no pattern or any pattern is empty, `None` is returned.
+    pub fn build(self) -> Option<BMByteSet> {
+        if self.patterns.is_empty() || self.patterns.iter().any(|p| p.is_empty()) {
+            return None;
+        }
+
+        let reversed_patterns =
+            self.patterns.iter().map(|p| p.iter().rev().copied().collect()).collect();
+
+        Some(BMByteSet {
+            forward:    WuManber::new(self.patterns),
+            reverse:    WuManber::new(reversed_patterns),
+            match_kind: self.match_kind,
+        })
+    }
+}
+
+impl BMByteSet {
+    /// Create a `BMByteSet` instance from patterns (the needles).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["oocoo", "cat"]).unwrap();
+    /// ```
+    #[inline]
+    pub fn from<I: IntoIterator<Item = T>, T: BMByteSearchable>(patterns: I) -> Option<BMByteSet> {
+        BMByteSet::builder(patterns).build()
+    }
+
+    /// Create a `BMByteSetBuilder` instance to build a `BMByteSet` instance with options.
+    #[inline]
+    pub fn builder<I: IntoIterator<Item = T>, T: BMByteSearchable>(
+        patterns: I,
+    ) -> BMByteSetBuilder {
+        BMByteSetBuilder {
+            patterns:   patterns.into_iter().map(|p| p.iter().copied().collect()).collect(),
+            match_kind: MatchKind::default(),
+        }
+    }
+
+    /// Get the pattern whose ID is `id`.
+    #[inline]
+    pub fn pattern(&self, id: usize) -> &[u8] {
+        &self.forward.patterns[id]
+    }
+
+    /// Get the number of the patterns.
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.forward.patterns.len()
+    }
+
+    /// Get how the matches are reported.
+    #[inline]
+    pub fn match_kind(&self) -> MatchKind {
+        self.match_kind
+    }
+
+    fn find_forward<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<(usize, usize)> {
+        self.forward.find(text.len(), |i| text.value_at(i), self.match_kind, limit)
+    }
+
+    fn find_backward<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<(usize, usize)> {
+        let text_len = text.len();
+
+        let mut result = self.reverse.find(
+            text_len,
+            |i| text.value_at(text_len - 1 - i),
+            self.match_kind,
+            limit,
+        );
+
+        for (id, position) in result.iter_mut() {
+            *position = text_len - *position - self.reverse.patterns[*id].len();
+        }
+
+        result
+    }
+}
+
+impl BMByteSet {
+    /// Find and return the IDs of the patterns and the positions of all matches in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["oocoo", "cat"]).unwrap();
+    ///
+    /// assert_eq!(vec![(0, 1), (1, 6), (0, 9)], set.find_all_in("coocoocatoocoo"));
+    /// ```
+    pub fn find_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<(usize, usize)> {
+        self.find_forward(text, 0)
+    }
+
+    /// Find and return the ID of the pattern and the position of the first match in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["oocoo", "cat"]).unwrap();
+    ///
+    /// assert_eq!(Some((1, 3)), set.find_first_in("coocatoocoo"));
+    /// ```
+    pub fn find_first_in<T: BMByteSearchable>(&self, text: T) -> Option<(usize, usize)> {
+        self.find_forward(text, 1).first().copied()
+    }
+
+    /// Find and return the IDs of the patterns and the positions of matches in any text (the haystack). If the `limit` is set to `0`, all matches will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["oocoo", "cat"]).unwrap();
+    ///
+    /// assert_eq!(vec![(0, 1), (1, 6)], set.find_in("coocoocatoocoo", 2));
+    /// ```
+    pub fn find_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<(usize, usize)> {
+        self.find_forward(text, limit)
+    }
+}
+
+impl BMByteSet {
+    /// Find and return the IDs of the patterns and the positions of all matches in any text (the haystack) from its tail to its head. The matches are chosen from the tail in the same way as `find_all_in` chooses them from the head, i.e. by their end positions.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["oocoo", "cat"]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![(0, 9), (1, 6), (0, 1)],
+    ///     set.rfind_all_in("coocoocatoocoo")
+    /// );
+    /// ```
+    pub fn rfind_all_in<T: BMByteSearchable>(&self, text: T) -> Vec<(usize, usize)> {
+        self.find_backward(text, 0)
+    }
+
+    /// Find and return the ID of the pattern and the position of the first match in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["oocoo", "cat"]).unwrap();
+    ///
+    /// assert_eq!(Some((1, 6)), set.rfind_first_in("coocoocatoo"));
+    /// ```
+    pub fn rfind_first_in<T: BMByteSearchable>(&self, text: T) -> Option<(usize, usize)> {
+        self.find_backward(text, 1).first().copied()
+    }
+
+    /// Find and return the IDs of the patterns and the positions of matches in any text (the haystack) from its tail to its head. If the `limit` is set to `0`, all matches will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["oocoo", "cat"]).unwrap();
+    ///
+    /// assert_eq!(vec![(0, 9), (1, 6)], set.rfind_in("coocoocatoocoo", 2));
+    /// ```
+    pub fn rfind_in<T: BMByteSearchable>(&self, text: T, limit: usize) -> Vec<(usize, usize)> {
+        self.find_backward(text, limit)
+    }
+}
+
+impl BMByteSet {
+    /// Replace the needles of `pairs` in any text (the haystack) with their replacements simultaneously, in a single scan from the head. The matches are chosen by `MatchKind::LeftmostLongest`, and the replaced bytes are never searched again, so a replacement cannot be matched by another needle. The pairs whose needles are empty are ignored.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let result = BMByteSet::replace_all("cat dog catalog", &[
+    ///     ("cat", "dog"),
+    ///     ("dog", "cat"),
+    ///     ("catalog", "list"),
+    /// ]);
+    ///
+    /// assert_eq!(b"dog cat list", result.as_slice());
+    /// ```
+    pub fn replace_all<T: BMByteSearchable, N: BMByteSearchable, R: BMByteSearchable>(
+        text: T,
+        pairs: &[(N, R)],
+    ) -> Vec<u8> {
+        let pairs: Vec<&(N, R)> = pairs.iter().filter(|(needle, _)| needle.len() > 0).collect();
+
+        match BMByteSet::builder(pairs.iter().map(|(needle, _)| needle.iter().as_slice()))
+            .match_kind(MatchKind::LeftmostLongest)
+            .build()
+        {
+            Some(set) => {
+                let replacements: Vec<&[u8]> =
+                    pairs.iter().map(|(_, replacement)| replacement.iter().as_slice()).collect();
+
+                set.replace_all_with(text, &replacements)
+            },
+            None => (0..text.len()).map(|i| text.value_at(i)).collect(),
+        }
+    }
+
+    /// Replace the patterns in any text (the haystack) with `replacements` (the `i`-th pattern with the `i`-th replacement) simultaneously, in a single scan from the head. The matches are chosen by the `MatchKind` of this set, and if it is `MatchKind::All`, the matches overlapping a replaced one are skipped.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMByteSet;
+    ///
+    /// let set = BMByteSet::from(["cat", "dog"]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     b"dog cat",
+    ///     set.replace_all_with("cat dog", &["dog", "cat"]).as_slice()
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of the replacements is not the number of the patterns.
+    pub fn replace_all_with<T: BMByteSearchable, R: BMByteSearchable>(
+        &self,
+        text: T,
+        replacements: &[R],
+    ) -> Vec<u8> {
+        assert_eq!(self.len(), replacements.len(), "every pattern needs a replacement");
+
+        let text_len = text.len();
+
+        let mut result = Vec::with_capacity(text_len);
+
+        // the bytes before this index have been handled
+        let mut handled = 0;
+
+        for (id, position) in self.find_forward(&text, 0) {
+            if position < handled {
+                continue;
+            }
+
+            result.extend((handled..position).map(|i| text.value_at(i)));
+            result.extend(replacements[id].iter());
+
+            handled = position + self.pattern(id).len();
+        }
+
+        result.extend((handled..text_len).map(|i| text.value_at(i)));
+
+        result
+    }
+}
@@ -0,0 +1,419 @@
+//! Scanning for file signatures (magic bytes), e.g. to carve embedded files out of disk images and firmware blobs. The magic bytes and the trailers of all signatures are searched together by one `BMByteSet` in a single pass, and then every magic is paired with the first trailer of its signature after it (or with its own trailer if the files of the signature can be nested). The magics found inside a file of the same signature are skipped because they belong to that file.
+
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::io::{self, ErrorKind, Read};
+
+use super::{BMByteSearchable, BMByteSet, MatchKind};
+
+/// A file signature, which is made of the magic bytes, the offset of the magic bytes from the start of the file, and optionally the trailer marking the end of the file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature<K> {
+    kind:              K,
+    magic:             Vec<u8>,
+    offset:            usize,
+    trailer:           Vec<u8>,
+    trailer_extra_len: usize,
+    max_len:           Option<usize>,
+    nested:            bool,
+}
+
+impl<K> Signature<K> {
+    /// Create a `Signature` instance whose hits are tagged with `kind`. The magic bytes are at the start of the file and there is no trailer. If the magic bytes are empty, `None` is returned.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::byte::Signature;
+    ///
+    /// let signature = Signature::new("bmp", "BM").unwrap();
+    /// ```
+    pub fn new<T: BMByteSearchable>(kind: K, magic: T) -> Option<Signature<K>> {
+        if magic.len() == 0 {
+            return None;
+        }
+
+        Some(Signature {
+            kind,
+            magic: magic.iter().copied().collect(),
+            offset: 0,
+            trailer: Vec::new(),
+            trailer_extra_len: 0,
+            max_len: None,
+            nested: false,
+        })
+    }
+
+    /// Set the offset of the magic bytes from the start of the file. For example, the magic bytes `ustar` of a tar archive are at the offset `257`.
+    #[inline]
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+
+        self
+    }
+
+    /// Set the trailer marking the end of the file, and the number of bytes after the trailer which still belong to the file. An empty trailer means no trailer.
+    #[inline]
+    pub fn trailer<T: BMByteSearchable>(mut self, trailer: T, extra_len: usize) -> Self {
+        self.trailer = trailer.iter().copied().collect();
+        self.trailer_extra_len = extra_len;
+
+        self
+    }
+
+    /// Set the maximum length of the file. If the file would be longer because the trailer is too far away (or missing), its end is unknown.
+    #[inline]
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+
+        self
+    }
+
+    /// Set whether the files of this signature may contain files of the same signature (e.g. the EXIF thumbnail of a JPEG file), whose trailers must not end the outer file. If it is `true`, the magics and the trailers are paired like brackets instead of pairing every magic with the first trailer after it.
+    #[inline]
+    pub fn nested(mut self, nested: bool) -> Self {
+        self.nested = nested;
+
+        self
+    }
+
+    /// Get the tag of the hits of this signature.
+    #[inline]
+    pub fn kind(&self) -> &K {
+        &self.kind
+    }
+}
+
+/// A file found by a `SignatureScanner`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SignatureHit<K> {
+    /// The tag of the signature.
+    pub kind:  K,
+    /// The position of the start of the file.
+    pub start: u64,
+    /// The position after the end of the file (after the trailer and its extra bytes). It is `None` if the signature has no trailer, or the trailer is not found (within the maximum length).
+    pub end:   Option<u64>,
+}
+
+/// The file formats of the built-in signatures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileFormat {
+    /// PNG, from the `89 50 4E 47 0D 0A 1A 0A` magic to the end of the `IEND` chunk.
+    Png,
+    /// JPEG, from the `FF D8 FF` magic (SOI) to the `FF D9` marker (EOI). The SOI and EOI markers of an embedded thumbnail are paired with each other, so the thumbnail does not end the file.
+    Jpeg,
+    /// ZIP, from the `PK\x03\x04` magic (a local file header) to the end of the end of central directory record (without the comment).
+    Zip,
+    /// ELF, from the `7F 45 4C 46` magic. Its end is unknown.
+    Elf,
+    /// PDF, from the `%PDF-` magic to the `%%EOF` marker.
+    Pdf,
+    /// gzip, from the `1F 8B 08` magic (a deflate member). Its end is unknown.
+    Gzip,
+}
+
+impl FileFormat {
+    /// Get the built-in signatures.
+    pub fn signatures() -> Vec<Signature<FileFormat>> {
+        vec![
+            Signature::new(FileFormat::Png, b"\x89PNG\r\n\x1a\n".as_slice())
+                .unwrap()
+                .trailer(b"IEND\xae\x42\x60\x82".as_slice(), 0),
+            Signature::new(FileFormat::Jpeg, b"\xff\xd8\xff".as_slice())
+                .unwrap()
+                .trailer(b"\xff\xd9".as_slice(), 0)
+                .nested(true),
+            Signature::new(FileFormat::Zip, b"PK\x03\x04".as_slice())
+                .unwrap()
+                .trailer(b"PK\x05\x06".as_slice(), 18),
+            Signature::new(FileFormat::Elf, b"\x7fELF".as_slice()).unwrap(),
+            Signature::new(FileFormat::Pdf, "%PDF-").unwrap().trailer("%%EOF", 0),
+            Signature::new(FileFormat::Gzip, b"\x1f\x8b\x08".as_slice()).unwrap(),
+        ]
+    }
+}
+
+/// Scan for the files of many signatures at once. The magic bytes and the trailers are searched in a single pass, and every occurrence of the magic bytes is reported as a hit ending after the first occurrence of its trailer after the magic bytes (or after its paired trailer for a nested signature). The occurrences of the magic bytes inside a hit of the same signature (e.g. the local file headers of the other entries of a ZIP archive) are not reported because they belong to that file.
+///
+/// ```
+/// use boyer_moore_magiclen::byte::{
+///     FileFormat, SignatureHit, SignatureScanner,
+/// };
+///
+/// let scanner = SignatureScanner::builtin();
+///
+/// let blob = b"junk%PDF-1.7 ...%%EOF\njunk\x7fELF...";
+///
+/// assert_eq!(
+///     vec![
+///         SignatureHit {
+///             kind: FileFormat::Pdf, start: 4, end: Some(21)
+///         },
+///         SignatureHit {
+///             kind: FileFormat::Elf, start: 26, end: None
+///         },
+///     ],
+///     scanner.scan(blob.as_slice())
+/// );
+/// ```
+#[derive(Debug)]
+pub struct SignatureScanner<K> {
+    signatures: Vec<Signature<K>>,
+    /// The magic bytes of the signatures, followed by the trailers.
+    set:        BMByteSet,
+    /// The index of the signature of every trailer in `set`.
+    trailer_of: Vec<usize>,
+}
+
+/// The positions of the magic bytes and the trailers found in a text.
+#[derive(Debug, Default)]
+struct Occurrences {
+    /// The indices of the signatures and the positions.
+    magics:   Vec<(usize, u64)>,
+    /// The positions of the trailer of every signature.
+    trailers: Vec<Vec<u64>>,
+}
+
+impl SignatureScanner<FileFormat> {
+    /// Create a `SignatureScanner` instance with the built-in signatures of `FileFormat`.
+    #[inline]
+    pub fn builtin() -> SignatureScanner<FileFormat> {
+        SignatureScanner::new(FileFormat::signatures()).unwrap()
+    }
+}
+
+impl<K: Clone> SignatureScanner<K> {
+    /// Create a `SignatureScanner` instance. If there is no signature, `None` is returned.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::byte::{Signature, SignatureScanner};
+    ///
+    /// let scanner = SignatureScanner::new(vec![
+    ///     Signature::new("tar", "ustar").unwrap().offset(257),
+    ///     Signature::new("gif", "GIF89a").unwrap().trailer("\x00\x3b", 0),
+    /// ])
+    /// .unwrap();
+    /// ```
+    pub fn new(signatures: Vec<Signature<K>>) -> Option<SignatureScanner<K>> {
+        let mut patterns: Vec<&[u8]> = signatures.iter().map(|s| s.magic.as_slice()).collect();
+        let mut trailer_of = Vec::new();
+
+        for (index, signature) in signatures.iter().enumerate() {
+            if !signature.trailer.is_empty() {
+                patterns.push(&signature.trailer);
+                trailer_of.push(index);
+            }
+        }
+
+        let set = BMByteSet::builder(patterns).match_kind(MatchKind::All).build()?;
+
+        Some(SignatureScanner {
+            signatures,
+            set,
+            trailer_of,
+        })
+    }
+
+    /// Get the signatures.
+    #[inline]
+    pub fn signatures(&self) -> &[Signature<K>] {
+        &self.signatures
+    }
+
+    /// Record an occurrence found by `set`.
+    #[inline]
+    fn record(&self, occurrences: &mut Occurrences, id: usize, position: u64) {
+        let signature_count = self.signatures.len();
+
+        if id < signature_count {
+            occurrences.magics.push((id, position));
+        } else {
+            occurrences.trailers[self.trailer_of[id - signature_count]].push(position);
+        }
+    }
+
+    /// Pair the magic bytes with the trailers, in a text of `text_len` bytes.
+    fn resolve(&self, mut occurrences: Occurrences, text_len: u64) -> Vec<SignatureHit<K>> {
+        occurrences.magics.sort_unstable_by_key(|&(index, position)| (position, index));
+
+        for trailers in occurrences.trailers.iter_mut() {
+            trailers.sort_unstable();
+        }
+
+        // the paired trailers of the magics of every nested signature, in the order of the magics
+        let mut nested_trailers: Vec<vec::IntoIter<Option<u64>>> = self
+            .signatures
+            .iter()
+            .enumerate()
+            .map(|(index, signature)| {
+                if signature.nested {
+                    let magics: Vec<u64> = occurrences
+                        .magics
+                        .iter()
+                        .filter(|&&(i, _)| i == index)
+                        .map(|&(_, position)| position)
+                        .collect();
+
+                    pair_nested(signature.magic.len(), &magics, &occurrences.trailers[index])
+                        .into_iter()
+                } else {
+                    Vec::new().into_iter()
+                }
+            })
+            .collect();
+
+        // the end of the last hit of every signature, before which its magics are skipped
+        let mut covered_until = vec![0u64; self.signatures.len()];
+
+        let mut result = Vec::with_capacity(occurrences.magics.len());
+
+        for (index, position) in occurrences.magics {
+            let signature = &self.signatures[index];
+
+            let nested_trailer = nested_trailers[index].next().flatten();
+
+            let start = match position.checked_sub(signature.offset as u64) {
+                Some(start) if start >= covered_until[index] => start,
+                _ => continue,
+            };
+
+            let trailer = if signature.nested {
+                nested_trailer
+            } else {
+                let trailers = &occurrences.trailers[index];
+
+                let magic_end = position + signature.magic.len() as u64;
+
+                trailers.get(trailers.partition_point(|&t| t < magic_end)).copied()
+            };
+
+            let end = trailer
+                .map(|t| {
+                    (t + (signature.trailer.len() + signature.trailer_extra_len) as u64)
+                        .min(text_len)
+                })
+                .filter(|&end| {
+                    signature.max_len.map_or(true, |max_len| end - start <= max_len as u64)
+                });
+
+            if let Some(end) = end {
+                covered_until[index] = end;
+            }
+
+            result.push(SignatureHit {
+                kind: signature.kind.clone(),
+                start,
+                end,
+            });
+        }
+
+        result
+    }
+
+    /// Scan any text (the haystack) and return the hits ordered by their start positions.
+    pub fn scan<T: BMByteSearchable>(&self, text: T) -> Vec<SignatureHit<K>> {
+        let mut occurrences = Occurrences {
+            magics:   Vec::new(),
+            trailers: vec![Vec::new(); self.signatures.len()],
+        };
+
+        for (id, position) in self.set.find_all_in(&text) {
+            self.record(&mut occurrences, id, position as u64);
+        }
+
+        self.resolve(occurrences, text.len() as u64)
+    }
+
+    /// Scan any `std::io::Read` stream without loading the whole stream into memory, and return the hits ordered by their start positions.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::byte::{FileFormat, SignatureScanner};
+    ///
+    /// let scanner = SignatureScanner::builtin();
+    ///
+    /// let reader = b"\0\0\xff\xd8\xff\xe0...\xff\xd9\0".as_slice();
+    ///
+    /// let hits = scanner.scan_reader(reader).unwrap();
+    ///
+    /// assert_eq!(FileFormat::Jpeg, hits[0].kind);
+    /// assert_eq!((2, Some(11)), (hits[0].start, hits[0].end));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn scan_reader<R: Read>(&self, mut reader: R) -> io::Result<Vec<SignatureHit<K>>> {
+        let mut occurrences = Occurrences {
+            magics:   Vec::new(),
+            trailers: vec![Vec::new(); self.signatures.len()],
+        };
+
+        // the bytes which may be the head of an occurrence are carried to the next buffer
+        let carry_len = (0..self.set.len()).map(|id| self.set.pattern(id).len()).max().unwrap() - 1;
+
+        let mut buffer = Vec::with_capacity(carry_len + super::stream::DEFAULT_BUFFER_SIZE);
+        let mut buffer_position = 0u64;
+
+        loop {
+            let searched_len = buffer.len();
+
+            let mut eof = false;
+
+            buffer.resize(carry_len + super::stream::DEFAULT_BUFFER_SIZE, 0);
+
+            let mut read_len = searched_len;
+
+            while read_len < buffer.len() {
+                match reader.read(&mut buffer[read_len..]) {
+                    Ok(0) => {
+                        eof = true;
+
+                        break;
+                    },
+                    Ok(c) => read_len += c,
+                    Err(ref err) if err.kind() == ErrorKind::Interrupted => (),
+                    Err(err) => return Err(err),
+                }
+            }
+
+            buffer.truncate(read_len);
+
+            // the occurrences which end in the carried bytes have been recorded
+            for (id, position) in self.set.find_all_in(buffer.as_slice()) {
+                if position + self.set.pattern(id).len() > searched_len {
+                    self.record(&mut occurrences, id, buffer_position + position as u64);
+                }
+            }
+
+            if eof {
+                break;
+            }
+
+            let drop_len = buffer.len() - carry_len.min(buffer.len());
+
+            buffer.drain(..drop_len);
+
+            buffer_position += drop_len as u64;
+        }
+
+        Ok(self.resolve(occurrences, buffer_position + buffer.len() as u64))
+    }
+}
+
+/// Pair the magics of a nested signature with its trailers like brackets, i.e. every trailer is paired with the last magic before it which has not been paired. The positions must be sorted. The positions of the paired trailers of the magics are returned in the order of the magics.
+fn pair_nested(magic_len: usize, magics: &[u64], trailers: &[u64]) -> Vec<Option<u64>> {
+    let mut result = vec![None; magics.len()];
+
+    // the indices of the magics which have not been paired
+    let mut open = Vec::new();
+    let mut next_magic = 0;
+
+    for &trailer in trailers {
+        while next_magic < magics.len() && magics[next_magic] + magic_len as u64 <= trailer {
+            open.push(next_magic);
+
+            next_magic += 1;
+        }
+
+        if let Some(magic) = open.pop() {
+            result[magic] = Some(trailer);
+        }
+    }
+
+    result
+}
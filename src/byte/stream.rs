@@ -0,0 +1,217 @@
+//! Searching any `std::io::Read` stream. The stream is read into a buffer of a fixed size, and the last `pattern.len() - 1` bytes of the buffer are carried to the next refill so that the matches straddling two refills are not missed. A match always ends in the newly read bytes, so no match is found twice.
+
+use std::{
+    collections::VecDeque,
+    io::{ErrorKind, Read},
+};
+
+use super::BMByte;
+
+/// The default number of bytes read from the stream on each refill.
+pub(super) const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Search a `BMByte` pattern in any `std::io::Read` stream, without loading the whole stream into memory. It is an iterator over the absolute positions (from the head of the stream) of the matched sub-sequences. If an I/O error occurs, the error is yielded and the iteration ends.
+///
+/// ```
+/// use boyer_moore_magiclen::{BMByte, StreamSearcher};
+///
+/// let bmb = BMByte::from("oocoo").unwrap();
+///
+/// let reader = "coocoocoocoo".as_bytes();
+///
+/// let result: Vec<u64> =
+///     StreamSearcher::new(&bmb, reader).map(|r| r.unwrap()).collect();
+///
+/// assert_eq!(vec![1, 7], result);
+/// ```
+#[derive(Debug)]
+pub struct StreamSearcher<'a, R: Read> {
+    bmb:         &'a BMByte,
+    reader:      R,
+    full:        bool,
+    buffer_size: usize,
+    buffer:      StreamBuffer,
+    finished:    bool,
+}
+
+/// The bytes read from a stream which have not been dropped, and the matches found in them.
+#[derive(Debug, Default)]
+pub(super) struct StreamBuffer {
+    pub(super) bytes:   Vec<u8>,
+    /// The position of `bytes[0]` in the stream.
+    offset:             u64,
+    /// The matches before this position overlap the last found match (only used if `full` is `false`).
+    next_free:          u64,
+    pub(super) results: VecDeque<u64>,
+}
+
+impl StreamBuffer {
+    /// Get the number of bytes which have been read from the stream.
+    #[inline]
+    pub(super) fn bytes_read(&self) -> u64 {
+        self.offset + self.bytes.len() as u64
+    }
+
+    /// Search the bytes, and then drop the bytes which can no longer be the head of a match.
+    pub(super) fn search(&mut self, bmb: &BMByte, full: bool) {
+        let pattern_len = bmb.pattern.len();
+
+        if full {
+            self.results.extend(
+                bmb.find_full_all_in(self.bytes.as_slice())
+                    .into_iter()
+                    .map(|i| self.offset + i as u64),
+            );
+        } else {
+            let start = (self.next_free.saturating_sub(self.offset) as usize).min(self.bytes.len());
+
+            let start_offset = self.offset + start as u64;
+
+            self.results.extend(
+                bmb.find_all_in(&self.bytes[start..]).into_iter().map(|i| start_offset + i as u64),
+            );
+
+            if let Some(&last) = self.results.back() {
+                self.next_free = self.next_free.max(last + pattern_len as u64);
+            }
+        }
+
+        let keep = (pattern_len - 1).min(self.bytes.len());
+        let consumed = self.bytes.len() - keep;
+
+        self.bytes.drain(..consumed);
+        self.offset += consumed as u64;
+    }
+}
+
+impl<'a, R: Read> StreamSearcher<'a, R> {
+    /// Create a `StreamSearcher` instance to search a `BMByte` pattern in a stream. The overlapping matches are not included by default.
+    #[inline]
+    pub fn new(bmb: &'a BMByte, reader: R) -> StreamSearcher<'a, R> {
+        StreamSearcher {
+            bmb,
+            reader,
+            full: false,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            buffer: StreamBuffer::default(),
+            finished: false,
+        }
+    }
+
+    /// Set whether to include the overlapping matches, like the `find_full_all_in` method instead of the `find_all_in` method.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, StreamSearcher};
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// let reader = "coocoocoocoo".as_bytes();
+    ///
+    /// let result: Vec<u64> = StreamSearcher::new(&bmb, reader)
+    ///     .full(true)
+    ///     .map(|r| r.unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![1, 4, 7], result);
+    /// ```
+    #[inline]
+    pub fn full(mut self, full: bool) -> Self {
+        self.full = full;
+
+        self
+    }
+
+    /// Set the number of bytes read from the stream on each refill. The default size is 64 KiB. A size of `0` is treated as `1`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, StreamSearcher};
+    ///
+    /// let bmb = BMByte::from("oocoo").unwrap();
+    ///
+    /// let reader = "coocoocoocoo".as_bytes();
+    ///
+    /// let result: Vec<u64> = StreamSearcher::new(&bmb, reader)
+    ///     .buffer_size(3)
+    ///     .map(|r| r.unwrap())
+    ///     .collect();
+    ///
+    /// assert_eq!(vec![1, 7], result);
+    /// ```
+    #[inline]
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+
+        self
+    }
+
+    /// Get the number of bytes which have been read from the stream.
+    #[inline]
+    pub fn bytes_read(&self) -> u64 {
+        self.buffer.bytes_read()
+    }
+
+    /// Unwrap this `StreamSearcher`, returning the underlying reader.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Read from the stream until `buffer_size` new bytes are appended to the buffer or the end of the stream is reached. Return the number of the new bytes.
+    fn refill(&mut self) -> std::io::Result<usize> {
+        let bytes = &mut self.buffer.bytes;
+
+        let old_len = bytes.len();
+        let target_len = old_len + self.buffer_size;
+
+        bytes.resize(target_len, 0);
+
+        let mut len = old_len;
+
+        let result = loop {
+            if len == target_len {
+                break Ok(());
+            }
+
+            match self.reader.read(&mut bytes[len..]) {
+                Ok(0) => {
+                    self.finished = true;
+
+                    break Ok(());
+                },
+                Ok(c) => len += c,
+                Err(ref err) if err.kind() == ErrorKind::Interrupted => (),
+                Err(err) => break Err(err),
+            }
+        };
+
+        bytes.truncate(len);
+
+        result.map(|_| len - old_len)
+    }
+}
+
+impl<'a, R: Read> Iterator for StreamSearcher<'a, R> {
+    type Item = std::io::Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(position) = self.buffer.results.pop_front() {
+                return Some(Ok(position));
+            }
+
+            if self.finished {
+                return None;
+            }
+
+            match self.refill() {
+                Ok(0) => (),
+                Ok(_) => self.buffer.search(self.bmb, self.full),
+                Err(err) => {
+                    self.finished = true;
+
+                    return Some(Err(err));
+                },
+            }
+        }
+    }
+}
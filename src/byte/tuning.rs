@@ -0,0 +1,199 @@
+use alloc::vec::Vec;
+use core::cell::Cell;
+
+use super::{Algorithm, BMByte, BMByteSearchable, ScanText};
+use crate::{bit_parallel, good_suffix};
+
+/// Why `BMByte::tuned` chose its algorithm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BMByteTuningReason {
+    /// The pattern has only one byte, so no algorithm can shift further than one byte and the tightest scan loop (Tuned Boyer-Moore) is used.
+    SingleByte,
+    /// The pattern is highly periodic (its smallest period is at most half of its length), so the good suffix rule is used to get larger shifts.
+    Periodic,
+    /// Every candidate (including Shift-Or and BNDM if the pattern is not longer than 64 bytes) has scanned the sample haystack and the one which read the fewest bytes was chosen. The reads are counted, not timed, so this is a heuristic: it favors the algorithms which skip more, even if another one would run faster on the machine. In particular, Shift-Or reads every byte of the sample, so it only wins if no other candidate skips any byte, although its branchless loop is often the fastest for very short patterns (which is why `BMByte::from` chooses it for them).
+    FewestReads,
+    /// No usable sample haystack was given and the pattern is long but made of very few distinct bytes (e.g. DNA), so the good suffix rule is used to get larger shifts.
+    SmallAlphabet,
+    /// No usable sample haystack was given and nothing special was found in the pattern, so the algorithm which `BMByte::from` would choose is used (see `BMByteBuilder::algorithm`).
+    Default,
+}
+
+/// The analysis made by `BMByte::tuned`, which can be obtained by `BMByte::tuning`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BMByteTuning {
+    /// The chosen algorithm.
+    pub algorithm:             Algorithm,
+    /// Whether the good suffix rule is used.
+    pub good_suffix:           bool,
+    /// Why the algorithm was chosen.
+    pub reason:                BMByteTuningReason,
+    /// The length of the pattern.
+    pub pattern_len:           usize,
+    /// The number of distinct bytes in the pattern.
+    pub alphabet_size:         usize,
+    /// The smallest period of the pattern.
+    pub period:                usize,
+    /// The number of occurrences of the last byte of the pattern in the sample haystack. The rarer it is, the more the bad character rule can skip. It is only informational: the choice does not read it, because the read counts of the candidates already reflect it.
+    pub last_byte_occurrences: usize,
+    /// The number of bytes each candidate (the algorithm and whether the good suffix rule is used) read to scan the sample haystack. It is empty if the candidates did not scan the sample haystack.
+    pub read_counts:           Vec<(Algorithm, bool, usize)>,
+}
+
+/// The minimum length of the pattern to be considered for the good suffix rule when it is made of very few distinct bytes.
+const SMALL_ALPHABET_MIN_PATTERN_LEN: usize = 16;
+
+/// The maximum number of distinct bytes of a pattern which is considered to have a small alphabet.
+const SMALL_ALPHABET_MAX_SIZE: usize = 4;
+
+const CANDIDATES: [(Algorithm, bool); 6] = [
+    (Algorithm::MagicLen, false),
+    (Algorithm::MagicLen, true),
+    (Algorithm::Horspool, false),
+    (Algorithm::Sunday, false),
+    (Algorithm::Raita, false),
+    (Algorithm::TunedBoyerMoore, false),
+];
+
+/// The candidates which are only compared if the pattern is not longer than `bit_parallel::MAX_PATTERN_LEN`.
+const BIT_PARALLEL_CANDIDATES: [(Algorithm, bool); 2] =
+    [(Algorithm::ShiftOr, false), (Algorithm::Bndm, false)];
+
+/// A text which counts how many bytes are read from it, to compare the candidates without timing them.
+struct CountingText<'a, T: BMByteSearchable> {
+    text:  &'a T,
+    reads: &'a Cell<usize>,
+}
+
+impl<T: BMByteSearchable> ScanText for CountingText<'_, T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> u8 {
+        self.reads.set(self.reads.get() + 1);
+
+        self.text.value_at(index)
+    }
+}
+
+impl BMByte {
+    /// Create a `BMByte` instance from a pattern (the needle) with the algorithm chosen by analysing the pattern (its length, the number of its distinct bytes and its periodicity) and a sample haystack (how many bytes each candidate algorithm reads to scan it, which already reflects how rare the bytes of the pattern are in it). Counting the reads is a heuristic which does not depend on the machine or on the timer, but it does not measure the time spent, so the chosen algorithm is not necessarily the fastest one. The sample can be empty if there is none. Use the `tuning` method to see what was chosen and why.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::{BMByte, BMByteTuningReason};
+    ///
+    /// let bmb =
+    ///     BMByte::tuned("oocoo", "coocoocoocoo and some other text").unwrap();
+    ///
+    /// assert_eq!(BMByteTuningReason::FewestReads, bmb.tuning().unwrap().reason);
+    /// assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
+    /// ```
+    pub fn tuned<TP: BMByteSearchable, TT: BMByteSearchable>(
+        pattern: TP,
+        sample: TT,
+    ) -> Option<BMByte> {
+        let pattern_len = pattern.len();
+
+        if pattern_len == 0 {
+            return None;
+        }
+
+        let alphabet_size = {
+            let mut seen = [false; 256];
+
+            for &c in pattern.iter() {
+                seen[c as usize] = true;
+            }
+
+            seen.iter().filter(|&&b| b).count()
+        };
+
+        let period = good_suffix::smallest_period(pattern_len, |i| pattern.value_at(i));
+
+        let last_pattern_char = pattern.value_at(pattern_len - 1);
+
+        let last_byte_occurrences =
+            (0..sample.len()).filter(|&i| sample.value_at(i) == last_pattern_char).count();
+
+        let mut read_counts = Vec::new();
+
+        let (algorithm, good_suffix, reason) = if pattern_len == 1 {
+            (Some(Algorithm::TunedBoyerMoore), false, BMByteTuningReason::SingleByte)
+        } else if period * 2 <= pattern_len {
+            (Some(Algorithm::MagicLen), true, BMByteTuningReason::Periodic)
+        } else if sample.len() >= pattern_len {
+            let reads = Cell::new(0);
+
+            let bit_parallel_candidates: &[(Algorithm, bool)] =
+                if pattern_len <= bit_parallel::MAX_PATTERN_LEN {
+                    &BIT_PARALLEL_CANDIDATES
+                } else {
+                    &[]
+                };
+
+            for &(algorithm, good_suffix) in CANDIDATES.iter().chain(bit_parallel_candidates) {
+                let bmb = BMByte::builder(&pattern)
+                    .rev(false)
+                    .algorithm(algorithm)
+                    .good_suffix(good_suffix)
+                    .build()?;
+
+                reads.set(0);
+
+                bmb.scan_forward(
+                    CountingText {
+                        text: &sample, reads: &reads
+                    },
+                    0,
+                    false,
+                );
+
+                read_counts.push((algorithm, good_suffix, reads.get()));
+            }
+
+            // the first candidate wins a tie
+            let &(algorithm, good_suffix, _) =
+                read_counts.iter().min_by_key(|(_, _, reads)| *reads).unwrap();
+
+            (Some(algorithm), good_suffix, BMByteTuningReason::FewestReads)
+        } else if alphabet_size <= SMALL_ALPHABET_MAX_SIZE
+            && pattern_len >= SMALL_ALPHABET_MIN_PATTERN_LEN
+        {
+            (Some(Algorithm::MagicLen), true, BMByteTuningReason::SmallAlphabet)
+        } else {
+            (None, false, BMByteTuningReason::Default)
+        };
+
+        let mut builder = BMByte::builder(&pattern).good_suffix(good_suffix);
+
+        if let Some(algorithm) = algorithm {
+            builder = builder.algorithm(algorithm);
+        }
+
+        let mut bmb = builder.build()?;
+
+        let algorithm = bmb.algorithm;
+
+        bmb.tuning = Some(BMByteTuning {
+            algorithm,
+            good_suffix,
+            reason,
+            pattern_len,
+            alphabet_size,
+            period,
+            last_byte_occurrences,
+            read_counts,
+        });
+
+        Some(bmb)
+    }
+
+    /// Get the analysis made by `BMByte::tuned`. If this instance was not created by `BMByte::tuned`, `None` is returned.
+    #[inline]
+    pub fn tuning(&self) -> Option<&BMByteTuning> {
+        self.tuning.as_ref()
+    }
+}
@@ -1,6 +1,11 @@
 use core::slice::Iter;
 use std::{collections::HashMap, ops::Deref};
 
+use self::folding::{FoldedChars, FullFoldedText, SimpleFoldedText};
+use crate::{bit_parallel, good_suffix};
+
+mod folding;
+
 #[allow(clippy::len_without_is_empty)]
 pub trait BMCharacterSearchable {
     fn len(&self) -> usize;
@@ -61,6 +66,26 @@ impl<T: BMCharacterSearchable> BMCharacterSearchable for &T {
     }
 }
 
+/// A text read by the scan loops, which only read it by `len` and `value_at`, so the texts whose characters are transformed when they are read (e.g. `SimpleFoldedText`) do not have to provide `iter`.
+#[allow(clippy::len_without_is_empty)]
+trait ScanText {
+    fn len(&self) -> usize;
+
+    fn value_at(&self, index: usize) -> char;
+}
+
+impl<T: BMCharacterSearchable> ScanText for T {
+    #[inline]
+    fn len(&self) -> usize {
+        BMCharacterSearchable::len(self)
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> char {
+        BMCharacterSearchable::value_at(self, index)
+    }
+}
+
 #[derive(Debug)]
 pub struct BMCharacterBadCharShiftMap {
     t: HashMap<char, usize>,
@@ -135,34 +160,878 @@ impl BMCharacterBadCharShiftMapRev {
             t: bad_char_shift_map
         })
     }
-}
+}
+
+#[derive(Debug)]
+pub struct BMCharacterGoodSuffixShiftMap {
+    t: Vec<usize>,
+}
+
+impl Deref for BMCharacterGoodSuffixShiftMap {
+    type Target = [usize];
+
+    #[inline]
+    fn deref(&self) -> &[usize] {
+        self.t.as_slice()
+    }
+}
+
+#[derive(Debug)]
+pub struct BMCharacterGoodSuffixShiftMapRev {
+    t: Vec<usize>,
+}
+
+impl Deref for BMCharacterGoodSuffixShiftMapRev {
+    type Target = [usize];
+
+    #[inline]
+    fn deref(&self) -> &[usize] {
+        self.t.as_slice()
+    }
+}
+
+impl BMCharacterGoodSuffixShiftMap {
+    pub fn create_good_suffix_shift_map<T: BMCharacterSearchable>(
+        pattern: T,
+    ) -> Option<BMCharacterGoodSuffixShiftMap> {
+        let pattern_len = pattern.len();
+
+        if pattern_len == 0 {
+            return None;
+        }
+
+        Some(BMCharacterGoodSuffixShiftMap {
+            t: good_suffix::create_good_suffix_shift_table(pattern_len, |i| pattern.value_at(i)),
+        })
+    }
+}
+
+impl BMCharacterGoodSuffixShiftMapRev {
+    pub fn create_good_suffix_shift_map<T: BMCharacterSearchable>(
+        pattern: T,
+    ) -> Option<BMCharacterGoodSuffixShiftMapRev> {
+        let pattern_len = pattern.len();
+
+        if pattern_len == 0 {
+            return None;
+        }
+
+        Some(BMCharacterGoodSuffixShiftMapRev {
+            t: good_suffix::create_good_suffix_shift_table_rev(pattern_len, |i| {
+                pattern.value_at(i)
+            }),
+        })
+    }
+}
+
+/// The masks used by the BNDM algorithm, which is chosen instead of Boyer-Moore-MagicLen for short patterns. (Shift-Or is not used for characters because it has to look up the mask of every character of the text, which is slow with a `HashMap`.)
+#[derive(Debug)]
+struct BMCharacterBitMasks {
+    forward:  HashMap<char, u64>,
+    backward: HashMap<char, u64>,
+}
+
+impl BMCharacterBitMasks {
+    /// Create the masks of a pattern. If the pattern is empty or longer than `bit_parallel::MAX_PATTERN_LEN`, `None` is returned.
+    fn create_bit_masks(pattern: &[char]) -> Option<BMCharacterBitMasks> {
+        let pattern_len = pattern.len();
+
+        if pattern_len == 0 || pattern_len > bit_parallel::MAX_PATTERN_LEN {
+            return None;
+        }
+
+        let mut forward = HashMap::with_capacity(pattern_len);
+        let mut backward = HashMap::with_capacity(pattern_len);
+
+        bit_parallel::for_each_mask(
+            pattern_len,
+            |i| pattern[i],
+            |c, f, b| {
+                *forward.entry(c).or_insert(0) |= f;
+                *backward.entry(c).or_insert(0) |= b;
+            },
+        );
+
+        Some(BMCharacterBitMasks {
+            forward,
+            backward,
+        })
+    }
+
+    fn find<T: ScanText>(
+        &self,
+        text: T,
+        pattern_len: usize,
+        limit: usize,
+        full: bool,
+    ) -> Vec<usize> {
+        let text_len = text.len();
+
+        let mut result = vec![];
+
+        if text_len < pattern_len {
+            return result;
+        }
+
+        bit_parallel::bndm_find(
+            text_len,
+            |i| text.value_at(i),
+            pattern_len,
+            |c| self.backward.get(&c).copied().unwrap_or(0),
+            limit,
+            full,
+            &mut result,
+        );
+
+        result
+    }
+
+    /// The reversed text is searched for the reversed pattern, whose forward masks are the backward masks of the pattern.
+    fn rfind<T: ScanText>(
+        &self,
+        text: T,
+        pattern_len: usize,
+        limit: usize,
+        full: bool,
+    ) -> Vec<usize> {
+        let text_len = text.len();
+
+        let mut result = vec![];
+
+        if text_len < pattern_len {
+            return result;
+        }
+
+        let text_len_dec = text_len - 1;
+
+        bit_parallel::bndm_find(
+            text_len,
+            |i| text.value_at(text_len_dec - i),
+            pattern_len,
+            |c| self.forward.get(&c).copied().unwrap_or(0),
+            limit,
+            full,
+            &mut result,
+        );
+
+        let end_index = text_len - pattern_len;
+
+        for i in result.iter_mut() {
+            *i = end_index - *i;
+        }
+
+        result
+    }
+}
+
+/// Using Boyer-Moore-MagicLen to search character sub-sequences in any character sequence. Short patterns (not longer than 8 characters, like the automatic choice of `Algorithm::ShiftOr` for `BMByte`) are searched by the bit-parallel BNDM algorithm instead unless the good suffix rule is enabled.
+#[derive(Debug)]
+pub struct BMCharacter {
+    /// The shift maps are not built if `bit_masks` is used.
+    bad_char_shift_map:        Option<BMCharacterBadCharShiftMap>,
+    bad_char_shift_map_rev:    Option<BMCharacterBadCharShiftMapRev>,
+    good_suffix_shift_map:     Option<BMCharacterGoodSuffixShiftMap>,
+    good_suffix_shift_map_rev: Option<BMCharacterGoodSuffixShiftMapRev>,
+    bit_masks:                 Option<BMCharacterBitMasks>,
+    ignore_case:               bool,
+    full_case_folding:         bool,
+    /// The pattern, in the case folding if `ignore_case` is `true`.
+    pattern:                   Vec<char>,
+}
+
+impl BMCharacter {
+    /// Create a `BMCharacter` instance from a pattern (the search needle).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacter;
+    ///
+    /// let bmc = BMCharacter::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    /// ```
+    #[inline]
+    pub fn from<T: BMCharacterSearchable>(pattern: T) -> Option<BMCharacter> {
+        BMCharacter::builder(pattern).build()
+    }
+
+    /// Create a `BMCharacter` instance from a pattern (the search needle). If `rev` is `false`, the bad character shift map for searching backward will not be built and every `rfind_xxx` method will have to build a temporary one on each call, so only do that if the instance is mainly used for searching forward.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacter;
+    ///
+    /// let bmc = BMCharacter::from_with_rev(vec!['o', 'o', 'c', 'o', 'o'], false)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![7, 1],
+    ///     bmc.rfind_all_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
+    /// ```
+    #[inline]
+    pub fn from_with_rev<T: BMCharacterSearchable>(pattern: T, rev: bool) -> Option<BMCharacter> {
+        BMCharacter::builder(pattern).rev(rev).build()
+    }
+
+    /// Create a `BMCharacterBuilder` instance to build a `BMCharacter` instance with options.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacter;
+    ///
+    /// let bmc = BMCharacter::builder(vec!['o', 'o', 'c', 'o', 'o'])
+    ///     .rev(false)
+    ///     .good_suffix(true)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[inline]
+    pub fn builder<T: BMCharacterSearchable>(pattern: T) -> BMCharacterBuilder<T> {
+        BMCharacterBuilder {
+            pattern,
+            rev: true,
+            good_suffix: false,
+            ignore_case: false,
+            full_case_folding: false,
+        }
+    }
+
+    /// Whether the case of the characters is ignored.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacter;
+    ///
+    /// let bmc =
+    ///     BMCharacter::builder(vec!['Σ']).ignore_case(true).build().unwrap();
+    ///
+    /// assert!(bmc.ignore_case());
+    /// ```
+    #[inline]
+    pub fn ignore_case(&self) -> bool {
+        self.ignore_case
+    }
+
+    fn find_forward<T: BMCharacterSearchable>(
+        &self,
+        text: T,
+        limit: usize,
+        full: bool,
+    ) -> Vec<usize> {
+        if !self.ignore_case {
+            return self.scan_forward(text, limit, full);
+        }
+
+        if !self.full_case_folding {
+            return self.scan_forward(
+                SimpleFoldedText {
+                    text,
+                },
+                limit,
+                full,
+            );
+        }
+
+        let folded = FullFoldedText::new(text);
+
+        if folded.is_one_to_one() {
+            return self.scan_forward(&folded.chars, limit, full);
+        }
+
+        let pattern_len = self.pattern.len();
+
+        let mut result = vec![];
+
+        // the folded text is scanned from here for the next candidate, which is accepted only if it starts and ends at the boundaries of the original characters
+        let mut from = 0;
+
+        while let Some(&index) = self
+            .scan_forward(
+                FoldedChars {
+                    chars: &folded.chars[from..]
+                },
+                1,
+                true,
+            )
+            .first()
+        {
+            let index = from + index;
+
+            match folded.map(index, pattern_len) {
+                Some((start, _)) => {
+                    result.push(start);
+
+                    if result.len() == limit {
+                        break;
+                    }
+
+                    // the next match must not overlap this one unless `full` is set
+                    from = if full { index + 1 } else { index + pattern_len };
+                },
+                None => from = index + 1,
+            }
+        }
+
+        result
+    }
+
+    fn find_backward<T: BMCharacterSearchable>(
+        &self,
+        text: T,
+        limit: usize,
+        full: bool,
+    ) -> Vec<usize> {
+        if !self.ignore_case {
+            return self.scan_backward(text, limit, full);
+        }
+
+        if !self.full_case_folding {
+            return self.scan_backward(
+                SimpleFoldedText {
+                    text,
+                },
+                limit,
+                full,
+            );
+        }
+
+        let folded = FullFoldedText::new(text);
+
+        if folded.is_one_to_one() {
+            return self.scan_backward(&folded.chars, limit, full);
+        }
+
+        let pattern_len = self.pattern.len();
+
+        // the shift maps are resolved once instead of for every candidate
+        self.with_rev_shift_maps(|shift_maps| {
+            let mut result = vec![];
+
+            // the folded text is scanned up to here for the previous candidate, which is accepted only if it starts and ends at the boundaries of the original characters
+            let mut to = folded.chars.len();
+
+            while let Some(&index) = self
+                .scan_backward_with(
+                    FoldedChars {
+                        chars: &folded.chars[..to]
+                    },
+                    1,
+                    true,
+                    shift_maps,
+                )
+                .first()
+            {
+                match folded.map(index, pattern_len) {
+                    Some((start, _)) => {
+                        result.push(start);
+
+                        if result.len() == limit {
+                            break;
+                        }
+
+                        // the previous match must not overlap this one unless `full` is set
+                        to = if full { index + pattern_len - 1 } else { index };
+                    },
+                    None => to = index + pattern_len - 1,
+                }
+            }
+
+            result
+        })
+    }
+
+    #[inline]
+    fn scan_forward<T: ScanText>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        let bad_char_shift_map = match self.bad_char_shift_map.as_ref() {
+            Some(bad_char_shift_map) => bad_char_shift_map,
+            None => {
+                let bit_masks = self.bit_masks.as_ref().unwrap();
+
+                return bit_masks.find(text, self.pattern.len(), limit, full);
+            },
+        };
+
+        match self.good_suffix_shift_map.as_ref() {
+            Some(good_suffix_shift_map) => scan_find_with_good_suffix(
+                text,
+                &self.pattern,
+                bad_char_shift_map,
+                good_suffix_shift_map,
+                limit,
+                full,
+            ),
+            None => {
+                if full {
+                    scan_find_full(text, &self.pattern, bad_char_shift_map, limit)
+                } else {
+                    scan_find(text, &self.pattern, bad_char_shift_map, limit)
+                }
+            },
+        }
+    }
+
+    #[inline]
+    fn scan_backward<T: ScanText>(&self, text: T, limit: usize, full: bool) -> Vec<usize> {
+        self.with_rev_shift_maps(|shift_maps| {
+            self.scan_backward_with(text, limit, full, shift_maps)
+        })
+    }
+
+    /// Call `f` with the reversed shift maps, which are built for this call if the instance was built without them (`rev(false)`). If `bit_masks` is used, no shift map is needed and `None` is given.
+    fn with_rev_shift_maps<R>(
+        &self,
+        f: impl FnOnce(
+            Option<(&BMCharacterBadCharShiftMapRev, Option<&BMCharacterGoodSuffixShiftMapRev>)>,
+        ) -> R,
+    ) -> R {
+        if self.bit_masks.is_some() {
+            return f(None);
+        }
+
+        match self.bad_char_shift_map_rev.as_ref() {
+            Some(bad_char_shift_map_rev) => {
+                f(Some((bad_char_shift_map_rev, self.good_suffix_shift_map_rev.as_ref())))
+            },
+            None => {
+                let bad_char_shift_map_rev =
+                    BMCharacterBadCharShiftMapRev::create_bad_char_shift_map(&self.pattern)
+                        .unwrap();
+
+                if self.good_suffix_shift_map.is_some() {
+                    let good_suffix_shift_map_rev =
+                        BMCharacterGoodSuffixShiftMapRev::create_good_suffix_shift_map(
+                            &self.pattern,
+                        )
+                        .unwrap();
+
+                    f(Some((&bad_char_shift_map_rev, Some(&good_suffix_shift_map_rev))))
+                } else {
+                    f(Some((&bad_char_shift_map_rev, None)))
+                }
+            },
+        }
+    }
+
+    fn scan_backward_with<T: ScanText>(
+        &self,
+        text: T,
+        limit: usize,
+        full: bool,
+        shift_maps: Option<(
+            &BMCharacterBadCharShiftMapRev,
+            Option<&BMCharacterGoodSuffixShiftMapRev>,
+        )>,
+    ) -> Vec<usize> {
+        match shift_maps {
+            None => {
+                let bit_masks = self.bit_masks.as_ref().unwrap();
+
+                bit_masks.rfind(text, self.pattern.len(), limit, full)
+            },
+            Some((bad_char_shift_map_rev, Some(good_suffix_shift_map_rev))) => {
+                scan_rfind_with_good_suffix(
+                    text,
+                    &self.pattern,
+                    bad_char_shift_map_rev,
+                    good_suffix_shift_map_rev,
+                    limit,
+                    full,
+                )
+            },
+            Some((bad_char_shift_map_rev, None)) => {
+                if full {
+                    scan_rfind_full(text, &self.pattern, bad_char_shift_map_rev, limit)
+                } else {
+                    scan_rfind(text, &self.pattern, bad_char_shift_map_rev, limit)
+                }
+            },
+        }
+    }
+}
+
+/// A builder to create a `BMCharacter` instance with options.
+#[derive(Debug, Clone)]
+pub struct BMCharacterBuilder<T: BMCharacterSearchable> {
+    pattern:           T,
+    rev:               bool,
+    good_suffix:       bool,
+    ignore_case:       bool,
+    full_case_folding: bool,
+}
+
+impl<T: BMCharacterSearchable> BMCharacterBuilder<T> {
+    /// Whether to build the shift maps for searching backward. If it is set to `false`, every `rfind_xxx` method will have to build temporary ones on each call. The default value is `true`.
+    #[inline]
+    pub fn rev(mut self, rev: bool) -> Self {
+        self.rev = rev;
+
+        self
+    }
+
+    /// Whether to also use the good suffix rule of the Boyer-Moore algorithm. The shift will be the maximum of the shift by the bad character rule and the shift by the good suffix rule, which is usually larger for long and repetitive patterns. The default value is `false`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacter;
+    ///
+    /// let bmc = BMCharacter::builder(vec!['o', 'o', 'c', 'o', 'o'])
+    ///     .good_suffix(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![1, 4, 7],
+    ///     bmc.find_full_all_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
+    /// ```
+    #[inline]
+    pub fn good_suffix(mut self, good_suffix: bool) -> Self {
+        self.good_suffix = good_suffix;
+
+        self
+    }
+
+    /// Whether to ignore the case of the characters by the Unicode simple case folding (e.g. `K`, `k` and the Kelvin sign `K` are the same). The shift maps are built over the folded pattern, and the characters of the text are folded before being compared. The default value is `false`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacter;
+    ///
+    /// let bmc = BMCharacter::builder(vec!['ο', 'δ', 'ό', 'ς'])
+    ///     .ignore_case(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![0, 5],
+    ///     bmc.find_all_in("ΟΔΌΣ οδόσ".chars().collect::<Vec<char>>())
+    /// );
+    /// ```
+    #[inline]
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+
+        self
+    }
+
+    /// Whether to use the Unicode full case folding instead of the simple one, which may fold a character into several characters (e.g. `ß` into `ss`). It implies the `ignore_case` option. The positions are still reported in the original text, and a match has to start and end at the boundaries of the original characters, so `s` does not match a half of `ß`. The default value is `false`.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacter;
+    ///
+    /// let bmc = BMCharacter::builder(vec!['s', 't', 'r', 'a', 's', 's', 'e'])
+    ///     .full_case_folding(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![0, 7],
+    ///     bmc.find_all_in("Straße STRASSE".chars().collect::<Vec<char>>())
+    /// );
+    /// ```
+    #[inline]
+    pub fn full_case_folding(mut self, full_case_folding: bool) -> Self {
+        self.full_case_folding = full_case_folding;
+
+        self
+    }
+
+    /// Create a `BMCharacter` instance. If the pattern is empty, `None` is returned.
+    pub fn build(self) -> Option<BMCharacter> {
+        let ignore_case = self.ignore_case || self.full_case_folding;
+
+        let pattern: Vec<char> = if self.full_case_folding {
+            let mut pattern = Vec::with_capacity(self.pattern.len());
+
+            for &c in self.pattern.iter() {
+                folding::fold_full(c, &mut pattern);
+            }
+
+            pattern
+        } else if ignore_case {
+            self.pattern.iter().map(|&c| folding::fold_simple(c)).collect()
+        } else {
+            self.pattern.iter().copied().collect()
+        };
+
+        let bit_masks = if self.good_suffix || pattern.len() > bit_parallel::AUTO_MAX_PATTERN_LEN {
+            None
+        } else {
+            BMCharacterBitMasks::create_bit_masks(&pattern)
+        };
+
+        let (bad_char_shift_map, bad_char_shift_map_rev) = if bit_masks.is_some() {
+            (None, None)
+        } else {
+            (
+                Some(BMCharacterBadCharShiftMap::create_bad_char_shift_map(&pattern)?),
+                if self.rev {
+                    Some(BMCharacterBadCharShiftMapRev::create_bad_char_shift_map(&pattern)?)
+                } else {
+                    None
+                },
+            )
+        };
+
+        let (good_suffix_shift_map, good_suffix_shift_map_rev) = if self.good_suffix {
+            (
+                Some(BMCharacterGoodSuffixShiftMap::create_good_suffix_shift_map(&pattern)?),
+                if self.rev {
+                    Some(BMCharacterGoodSuffixShiftMapRev::create_good_suffix_shift_map(&pattern)?)
+                } else {
+                    None
+                },
+            )
+        } else {
+            (None, None)
+        };
+
+        Some(BMCharacter {
+            bad_char_shift_map,
+            bad_char_shift_map_rev,
+            good_suffix_shift_map,
+            good_suffix_shift_map_rev,
+            bit_masks,
+            ignore_case,
+            full_case_folding: self.full_case_folding,
+            pattern,
+        })
+    }
+}
+
+/// Using Boyer-Moore-MagicLen to search character sub-sequences in any character sequence, but only from the head to the tail. Only the bad character shift map for searching forward is built.
+#[derive(Debug)]
+pub struct BMCharacterForward {
+    bad_char_shift_map: BMCharacterBadCharShiftMap,
+    pattern:            Vec<char>,
+}
+
+impl BMCharacterForward {
+    /// Create a `BMCharacterForward` instance from a pattern (the search needle).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterForward;
+    ///
+    /// let bmc = BMCharacterForward::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    /// ```
+    pub fn from<T: BMCharacterSearchable>(pattern: T) -> Option<BMCharacterForward> {
+        let bad_char_shift_map = BMCharacterBadCharShiftMap::create_bad_char_shift_map(&pattern)?;
+
+        Some(BMCharacterForward {
+            bad_char_shift_map,
+            pattern: pattern.iter().copied().collect(),
+        })
+    }
+}
+
+impl BMCharacterForward {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterForward;
+    ///
+    /// let bmc = BMCharacterForward::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![1, 4, 7],
+    ///     bmc.find_full_all_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
+    /// ```
+    pub fn find_full_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
+        find_full(text, &self.pattern, &self.bad_char_shift_map, 0)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack). If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterForward;
+    ///
+    /// let bmc = BMCharacterForward::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![1, 4],
+    ///     bmc.find_full_in(
+    ///         vec!['c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'],
+    ///         2
+    ///     )
+    /// );
+    /// ```
+    pub fn find_full_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        find_full(text, &self.pattern, &self.bad_char_shift_map, limit)
+    }
+
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterForward;
+    ///
+    /// let bmc = BMCharacterForward::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![1, 7],
+    ///     bmc.find_all_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
+    /// ```
+    pub fn find_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
+        find(text, &self.pattern, &self.bad_char_shift_map, 0)
+    }
+
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterForward;
+    ///
+    /// let bmc = BMCharacterForward::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(1),
+    ///     bmc.find_first_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
+    /// ```
+    pub fn find_first_in<T: BMCharacterSearchable>(&self, text: T) -> Option<usize> {
+        find(text, &self.pattern, &self.bad_char_shift_map, 1).first().copied()
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterForward;
+    ///
+    /// let bmc = BMCharacterForward::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![1],
+    ///     bmc.find_in(
+    ///         vec!['c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'],
+    ///         1
+    ///     )
+    /// );
+    /// ```
+    pub fn find_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        find(text, &self.pattern, &self.bad_char_shift_map, limit)
+    }
+}
+
+/// Using Boyer-Moore-MagicLen to search character sub-sequences in any character sequence, but only from the tail to the head. Only the bad character shift map for searching backward is built.
+#[derive(Debug)]
+pub struct BMCharacterReverse {
+    bad_char_shift_map_rev: BMCharacterBadCharShiftMapRev,
+    pattern:                Vec<char>,
+}
+
+impl BMCharacterReverse {
+    /// Create a `BMCharacterReverse` instance from a pattern (the search needle).
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterReverse;
+    ///
+    /// let bmc = BMCharacterReverse::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    /// ```
+    pub fn from<T: BMCharacterSearchable>(pattern: T) -> Option<BMCharacterReverse> {
+        let bad_char_shift_map_rev =
+            BMCharacterBadCharShiftMapRev::create_bad_char_shift_map(&pattern)?;
+
+        Some(BMCharacterReverse {
+            bad_char_shift_map_rev,
+            pattern: pattern.iter().copied().collect(),
+        })
+    }
+}
+
+impl BMCharacterReverse {
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterReverse;
+    ///
+    /// let bmc = BMCharacterReverse::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![7, 4, 1],
+    ///     bmc.rfind_full_all_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
+    /// ```
+    pub fn rfind_full_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
+        rfind_full(text, &self.pattern, &self.bad_char_shift_map_rev, 0)
+    }
+
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterReverse;
+    ///
+    /// let bmc = BMCharacterReverse::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![7, 4],
+    ///     bmc.rfind_full_in(
+    ///         vec!['c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'],
+    ///         2
+    ///     )
+    /// );
+    /// ```
+    pub fn rfind_full_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        rfind_full(text, &self.pattern, &self.bad_char_shift_map_rev, limit)
+    }
 
-/// Using Boyer-Moore-MagicLen to search character sub-sequences in any character sequence.
-#[derive(Debug)]
-pub struct BMCharacter {
-    bad_char_shift_map:     BMCharacterBadCharShiftMap,
-    bad_char_shift_map_rev: BMCharacterBadCharShiftMapRev,
-    pattern:                Vec<char>,
-}
+    /// Find and return the positions of all matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterReverse;
+    ///
+    /// let bmc = BMCharacterReverse::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![7, 1],
+    ///     bmc.rfind_all_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
+    /// ```
+    pub fn rfind_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
+        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 0)
+    }
 
-impl BMCharacter {
-    /// Create a `BMCharacter` instance from a pattern (the search needle).
+    /// Find and return the position of the first matched sub-sequence in any text (the haystack) from its tail to its head.
     ///
     /// ```
-    /// use boyer_moore_magiclen::BMCharacter;
+    /// use boyer_moore_magiclen::BMCharacterReverse;
     ///
-    /// let bmc = BMCharacter::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    /// let bmc = BMCharacterReverse::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(7),
+    ///     bmc.rfind_first_in(vec![
+    ///         'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'
+    ///     ])
+    /// );
     /// ```
-    pub fn from<T: BMCharacterSearchable>(pattern: T) -> Option<BMCharacter> {
-        let bad_char_shift_map = BMCharacterBadCharShiftMap::create_bad_char_shift_map(&pattern)?;
-        let bad_char_shift_map_rev =
-            BMCharacterBadCharShiftMapRev::create_bad_char_shift_map(&pattern)?;
+    pub fn rfind_first_in<T: BMCharacterSearchable>(&self, text: T) -> Option<usize> {
+        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 1).first().copied()
+    }
 
-        Some(BMCharacter {
-            bad_char_shift_map,
-            bad_char_shift_map_rev,
-            pattern: pattern.iter().copied().collect(),
-        })
+    /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
+    ///
+    /// ```
+    /// use boyer_moore_magiclen::BMCharacterReverse;
+    ///
+    /// let bmc = BMCharacterReverse::from(vec!['o', 'o', 'c', 'o', 'o']).unwrap();
+    ///
+    /// assert_eq!(
+    ///     vec![7],
+    ///     bmc.rfind_in(
+    ///         vec!['c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o', 'c', 'o', 'o'],
+    ///         1
+    ///     )
+    /// );
+    /// ```
+    pub fn rfind_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
+        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, limit)
     }
 }
 
@@ -182,7 +1051,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn find_full_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
-        find_full(text, &self.pattern, &self.bad_char_shift_map, 0)
+        self.find_forward(text, 0, true)
     }
 
     /// Find and return the positions of matched sub-sequences in any text (the haystack). If the `limit` is set to `0`, all sub-sequences will be found.
@@ -201,7 +1070,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn find_full_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        find_full(text, &self.pattern, &self.bad_char_shift_map, limit)
+        self.find_forward(text, limit, true)
     }
 }
 
@@ -221,7 +1090,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn rfind_full_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
-        rfind_full(text, &self.pattern, &self.bad_char_shift_map_rev, 0)
+        self.find_backward(text, 0, true)
     }
 
     /// Find and return the positions of matched sub-sequences in any text (the haystack) from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
@@ -240,7 +1109,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn rfind_full_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        rfind_full(text, &self.pattern, &self.bad_char_shift_map_rev, limit)
+        self.find_backward(text, limit, true)
     }
 }
 
@@ -249,6 +1118,15 @@ pub fn find_full<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
     pattern: TP,
     bad_char_shift_map: &BMCharacterBadCharShiftMap,
     limit: usize,
+) -> Vec<usize> {
+    scan_find_full(text, pattern, bad_char_shift_map, limit)
+}
+
+fn scan_find_full<TT: ScanText, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMap,
+    limit: usize,
 ) -> Vec<usize> {
     let text_len = text.len();
     let pattern_len = pattern.len();
@@ -330,6 +1208,15 @@ pub fn rfind_full<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
     pattern: TP,
     bad_char_shift_map: &BMCharacterBadCharShiftMapRev,
     limit: usize,
+) -> Vec<usize> {
+    scan_rfind_full(text, pattern, bad_char_shift_map, limit)
+}
+
+fn scan_rfind_full<TT: ScanText, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMapRev,
+    limit: usize,
 ) -> Vec<usize> {
     let text_len = text.len();
     let pattern_len = pattern.len();
@@ -429,7 +1316,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn find_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
-        find(text, &self.pattern, &self.bad_char_shift_map, 0)
+        self.find_forward(text, 0, false)
     }
 
     /// Find and return the position of the first matched sub-sequence in any text (the haystack).
@@ -447,7 +1334,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn find_first_in<T: BMCharacterSearchable>(&self, text: T) -> Option<usize> {
-        find(text, &self.pattern, &self.bad_char_shift_map, 1).first().copied()
+        self.find_forward(text, 1, false).first().copied()
     }
 
     /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap. If the `limit` is set to `0`, all sub-sequences will be found.
@@ -466,7 +1353,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn find_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        find(text, &self.pattern, &self.bad_char_shift_map, limit)
+        self.find_forward(text, limit, false)
     }
 }
 
@@ -486,7 +1373,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn rfind_all_in<T: BMCharacterSearchable>(&self, text: T) -> Vec<usize> {
-        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 0)
+        self.find_backward(text, 0, false)
     }
 
     /// Find and return the position of the first matched sub-sequence in any text (the haystack) from its tail to its head.
@@ -504,7 +1391,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn rfind_first_in<T: BMCharacterSearchable>(&self, text: T) -> Option<usize> {
-        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, 1).first().copied()
+        self.find_backward(text, 1, false).first().copied()
     }
 
     /// Find and return the positions of matched sub-sequences in any text (the haystack) but not including the overlap from its tail to its head. If the `limit` is set to `0`, all sub-sequences will be found.
@@ -523,7 +1410,7 @@ impl BMCharacter {
     /// );
     /// ```
     pub fn rfind_in<T: BMCharacterSearchable>(&self, text: T, limit: usize) -> Vec<usize> {
-        rfind(text, &self.pattern, &self.bad_char_shift_map_rev, limit)
+        self.find_backward(text, limit, false)
     }
 }
 
@@ -532,6 +1419,15 @@ pub fn find<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
     pattern: TP,
     bad_char_shift_map: &BMCharacterBadCharShiftMap,
     limit: usize,
+) -> Vec<usize> {
+    scan_find(text, pattern, bad_char_shift_map, limit)
+}
+
+fn scan_find<TT: ScanText, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMap,
+    limit: usize,
 ) -> Vec<usize> {
     let text_len = text.len();
     let pattern_len = pattern.len();
@@ -601,6 +1497,15 @@ pub fn rfind<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
     pattern: TP,
     bad_char_shift_map: &BMCharacterBadCharShiftMapRev,
     limit: usize,
+) -> Vec<usize> {
+    scan_rfind(text, pattern, bad_char_shift_map, limit)
+}
+
+fn scan_rfind<TT: ScanText, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMapRev,
+    limit: usize,
 ) -> Vec<usize> {
     let text_len = text.len();
     let pattern_len = pattern.len();
@@ -667,3 +1572,226 @@ pub fn rfind<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
 
     result
 }
+
+/// Search forward with the bad character rule of Boyer-Moore-MagicLen and the good suffix rule of Boyer-Moore. If `full` is `true`, the overlapping sub-sequences are included.
+pub fn find_with_good_suffix<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMap,
+    good_suffix_shift_map: &BMCharacterGoodSuffixShiftMap,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    scan_find_with_good_suffix(
+        text,
+        pattern,
+        bad_char_shift_map,
+        good_suffix_shift_map,
+        limit,
+        full,
+    )
+}
+
+fn scan_find_with_good_suffix<TT: ScanText, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMap,
+    good_suffix_shift_map: &BMCharacterGoodSuffixShiftMap,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+    let pattern_len_inc = pattern_len + 1;
+
+    let last_pattern_char = pattern.value_at(pattern_len_dec);
+
+    let mut shift = 0;
+
+    let end_index = text_len - pattern_len;
+
+    let mut result = vec![];
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate().rev() {
+            if text.value_at(shift + i) != pc {
+                let p = shift + pattern_len;
+                if p == text_len {
+                    break 'outer;
+                }
+                shift += bad_char_shift_map
+                    .get(&text.value_at(shift + pattern_len_dec))
+                    .copied()
+                    .unwrap_or(pattern_len)
+                    .max({
+                        let c = text.value_at(p);
+
+                        if c == last_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map.get(&c).map(|&c| c + 1).unwrap_or(pattern_len_inc)
+                        }
+                    })
+                    .max(good_suffix_shift_map[i]);
+                if shift > end_index {
+                    break 'outer;
+                }
+                continue 'outer;
+            }
+        }
+        result.push(shift);
+
+        if shift == end_index {
+            break;
+        }
+
+        if result.len() == limit {
+            break;
+        }
+
+        if full {
+            shift += bad_char_shift_map
+                .get(&text.value_at(shift + pattern_len_dec))
+                .copied()
+                .unwrap_or(pattern_len)
+                .max({
+                    let c = text.value_at(shift + pattern_len);
+
+                    if c == last_pattern_char {
+                        1
+                    } else {
+                        bad_char_shift_map.get(&c).map(|&c| c + 1).unwrap_or(pattern_len_inc)
+                    }
+                })
+                .max(good_suffix_shift_map[0]);
+        } else {
+            shift += pattern_len;
+        }
+        if shift > end_index {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Search backward with the bad character rule of Boyer-Moore-MagicLen and the good suffix rule of Boyer-Moore. If `full` is `true`, the overlapping sub-sequences are included.
+pub fn rfind_with_good_suffix<TT: BMCharacterSearchable, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMapRev,
+    good_suffix_shift_map: &BMCharacterGoodSuffixShiftMapRev,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    scan_rfind_with_good_suffix(
+        text,
+        pattern,
+        bad_char_shift_map,
+        good_suffix_shift_map,
+        limit,
+        full,
+    )
+}
+
+fn scan_rfind_with_good_suffix<TT: ScanText, TP: BMCharacterSearchable>(
+    text: TT,
+    pattern: TP,
+    bad_char_shift_map: &BMCharacterBadCharShiftMapRev,
+    good_suffix_shift_map: &BMCharacterGoodSuffixShiftMapRev,
+    limit: usize,
+    full: bool,
+) -> Vec<usize> {
+    let text_len = text.len();
+    let pattern_len = pattern.len();
+
+    if text_len == 0 || pattern_len == 0 || text_len < pattern_len {
+        return vec![];
+    }
+
+    let pattern_len_dec = pattern_len - 1;
+    let pattern_len_inc = pattern_len + 1;
+
+    let first_pattern_char = pattern.value_at(0);
+
+    let mut shift = text_len - 1;
+
+    let start_index = pattern_len_dec;
+
+    let mut result = vec![];
+
+    'outer: loop {
+        for (i, pc) in pattern.iter().copied().enumerate() {
+            if text.value_at(shift - pattern_len_dec + i) != pc {
+                if shift < pattern_len {
+                    break 'outer;
+                }
+                let s = bad_char_shift_map
+                    .get(&text.value_at(shift - pattern_len_dec))
+                    .copied()
+                    .unwrap_or(pattern_len)
+                    .max({
+                        let c = text.value_at(shift - pattern_len);
+
+                        if c == first_pattern_char {
+                            1
+                        } else {
+                            bad_char_shift_map.get(&c).map(|&c| c + 1).unwrap_or(pattern_len_inc)
+                        }
+                    })
+                    .max(good_suffix_shift_map[i]);
+                if shift < s {
+                    break 'outer;
+                }
+                shift -= s;
+                if shift < start_index {
+                    break 'outer;
+                }
+                continue 'outer;
+            }
+        }
+        result.push(shift - pattern_len_dec);
+
+        if shift == start_index {
+            break;
+        }
+
+        if result.len() == limit {
+            break;
+        }
+
+        let s = if full {
+            bad_char_shift_map
+                .get(&text.value_at(shift - pattern_len_dec))
+                .copied()
+                .unwrap_or(pattern_len)
+                .max({
+                    let c = text.value_at(shift - pattern_len);
+
+                    if c == first_pattern_char {
+                        1
+                    } else {
+                        bad_char_shift_map.get(&c).map(|&c| c + 1).unwrap_or(pattern_len_inc)
+                    }
+                })
+                .max(good_suffix_shift_map[pattern_len_dec])
+        } else {
+            pattern_len
+        };
+        if shift < s {
+            break;
+        }
+        shift -= s;
+        if shift < start_index {
+            break;
+        }
+    }
+
+    result
+}
@@ -0,0 +1,132 @@
+//! Unicode case folding for searching case-insensitively. The folding is derived from the case mappings of the standard library: a character is folded to the lowercase of its uppercase, so e.g. `Σ`, `σ` and `ς` are folded to the same character. The full folding may expand a character into several characters (e.g. `ß` into `ss`), while the simple folding keeps such a character (in lowercase if possible) so that the positions are not changed.
+
+use super::{BMCharacterSearchable, ScanText};
+
+/// Append the full case folding of a character to `output`. The characters which the lowercase of the uppercase yields are folded again until they no longer change, so that the folding is idempotent (e.g. `ẞ` is folded into `ß` first and then into `ss`, like `ß` itself).
+pub(super) fn fold_full(c: char, output: &mut Vec<char>) {
+    for u in c.to_uppercase() {
+        for l in u.to_lowercase() {
+            if l == c {
+                output.push(l);
+            } else {
+                fold_full(l, output);
+            }
+        }
+    }
+}
+
+/// Get the simple case folding of a character.
+#[inline]
+pub(super) fn fold_simple(c: char) -> char {
+    let mut upper = c.to_uppercase();
+
+    if upper.len() == 1 {
+        let mut lower = upper.next().unwrap().to_lowercase();
+
+        if lower.len() == 1 {
+            return lower.next().unwrap();
+        }
+    }
+
+    let mut lower = c.to_lowercase();
+
+    if lower.len() == 1 {
+        lower.next().unwrap()
+    } else {
+        c
+    }
+}
+
+/// A text whose characters are read in the simple case folding.
+pub(super) struct SimpleFoldedText<T: BMCharacterSearchable> {
+    pub(super) text: T,
+}
+
+impl<T: BMCharacterSearchable> ScanText for SimpleFoldedText<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> char {
+        fold_simple(self.text.value_at(index))
+    }
+}
+
+/// A part of the characters of a `FullFoldedText`, which is scanned for the next candidate.
+pub(super) struct FoldedChars<'a> {
+    pub(super) chars: &'a [char],
+}
+
+impl ScanText for FoldedChars<'_> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.chars.len()
+    }
+
+    #[inline]
+    fn value_at(&self, index: usize) -> char {
+        self.chars[index]
+    }
+}
+
+/// A text in the full case folding, with the positions of its characters in the original text.
+pub(super) struct FullFoldedText {
+    pub(super) chars: Vec<char>,
+    /// The position in the original text of the character which every folded character comes from.
+    origins:          Vec<usize>,
+    text_len:         usize,
+}
+
+impl FullFoldedText {
+    pub(super) fn new<T: BMCharacterSearchable>(text: T) -> FullFoldedText {
+        let text_len = text.len();
+
+        let mut chars = Vec::with_capacity(text_len);
+        let mut origins = Vec::with_capacity(text_len);
+
+        for (i, &c) in text.iter().enumerate() {
+            fold_full(c, &mut chars);
+
+            origins.resize(chars.len(), i);
+        }
+
+        FullFoldedText {
+            chars,
+            origins,
+            text_len,
+        }
+    }
+
+    /// Whether every character is folded into exactly one character, so that the positions are not changed.
+    #[inline]
+    pub(super) fn is_one_to_one(&self) -> bool {
+        self.chars.len() == self.text_len
+    }
+
+    /// Map a match in the folded text to the start and the end positions in the original text. If the match does not start and end at the boundaries of the original characters (e.g. `s` matching a half of `ß`), `None` is returned.
+    pub(super) fn map(&self, start: usize, len: usize) -> Option<(usize, usize)> {
+        let end = start + len;
+
+        let original_start = self.origins[start];
+
+        if start > 0 && self.origins[start - 1] == original_start {
+            return None;
+        }
+
+        let original_end = if end == self.chars.len() {
+            self.text_len
+        } else {
+            let original_end = self.origins[end];
+
+            if self.origins[end - 1] == original_end {
+                return None;
+            }
+
+            original_end
+        };
+
+        Some((original_start, original_end))
+    }
+}
@@ -0,0 +1,109 @@
+use alloc::vec::Vec;
+
+/// Compute the lengths of the longest common suffixes of every prefix of the pattern and the whole pattern. The pattern is accessed by `at` so that the same code works for the reversed pattern.
+fn suffixes<T: PartialEq>(m: usize, at: impl Fn(usize) -> T) -> Vec<usize> {
+    let mut suff = vec![0; m];
+
+    suff[m - 1] = m;
+
+    let m = m as isize;
+    let mut f = 0isize;
+    let mut g = m - 1;
+
+    for i in (0..m - 1).rev() {
+        if i > g && (suff[(i + m - 1 - f) as usize] as isize) < i - g {
+            suff[i as usize] = suff[(i + m - 1 - f) as usize];
+        } else {
+            if i < g {
+                g = i;
+            }
+
+            f = i;
+
+            while g >= 0 && at(g as usize) == at((g + m - 1 - f) as usize) {
+                g -= 1;
+            }
+
+            suff[i as usize] = (f - g) as usize;
+        }
+    }
+
+    suff
+}
+
+/// Create the good suffix shift table for a pattern which is compared from its tail to its head. The value at the index `i` is the shift to apply when a mismatch happens at the index `i` of the pattern. The value at the index `0` is also the shift to apply after a full match, which is the smallest period of the pattern.
+fn create<T: PartialEq>(m: usize, at: impl Fn(usize) -> T) -> Vec<usize> {
+    let suff = suffixes(m, &at);
+
+    let mut t = vec![m; m];
+
+    let mut j = 0;
+
+    for i in (0..m).rev() {
+        if suff[i] == i + 1 {
+            while j < m - 1 - i {
+                if t[j] == m {
+                    t[j] = m - 1 - i;
+                }
+
+                j += 1;
+            }
+        }
+    }
+
+    for (i, &s) in suff.iter().enumerate().take(m - 1) {
+        t[m - 1 - s] = m - 1 - i;
+    }
+
+    t
+}
+
+/// Create the good suffix shift table used for searching forward. `m` must not be `0`.
+#[inline]
+pub(crate) fn create_good_suffix_shift_table<T: PartialEq>(
+    m: usize,
+    at: impl Fn(usize) -> T,
+) -> Vec<usize> {
+    create(m, at)
+}
+
+/// Create the good suffix shift table used for searching backward (the pattern is compared from its head to its tail). The value at the index `i` is the shift to apply when a mismatch happens at the index `i` of the pattern. The value at the index `m - 1` is also the shift to apply after a full match. `m` must not be `0`.
+#[inline]
+pub(crate) fn create_good_suffix_shift_table_rev<T: PartialEq>(
+    m: usize,
+    at: impl Fn(usize) -> T,
+) -> Vec<usize> {
+    let mut t = create(m, |i| at(m - 1 - i));
+
+    t.reverse();
+
+    t
+}
+
+/// Compute the lengths of the longest proper borders of every non-empty prefix of the pattern, i.e. the `i`-th element is for the prefix whose length is `i + 1`. This is the failure function of Knuth-Morris-Pratt.
+pub(crate) fn borders<T: PartialEq>(m: usize, at: impl Fn(usize) -> T) -> Vec<usize> {
+    let mut border = vec![0; m];
+
+    let mut k = 0;
+
+    for i in 1..m {
+        let c = at(i);
+
+        while k > 0 && c != at(k) {
+            k = border[k - 1];
+        }
+
+        if c == at(k) {
+            k += 1;
+        }
+
+        border[i] = k;
+    }
+
+    border
+}
+
+/// Compute the smallest period of a pattern, which is its length minus the length of its longest proper border. `m` must not be `0`.
+pub(crate) fn smallest_period<T: PartialEq>(m: usize, at: impl Fn(usize) -> T) -> usize {
+    m - borders(m, at)[m - 1]
+}
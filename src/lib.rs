@@ -5,7 +5,7 @@ This crate can be used to search substrings in a string or search any sub-sequen
 
 ## Usage
 
-For binary data and UTF-8 data, use the `BMByte` struct. For character sequences, use the `BMCharacter` struct (however it is much slower than `BMByte`). The `BMCharacter` struct needs the standard library support, and you have to enable the `character` feature to make it available.
+For binary data and UTF-8 data, use the `BMByte` struct. For character sequences, use the `BMCharacter` struct (however it is much slower than `BMByte`). The `BMCharacter` struct needs the standard library support, and you have to enable the `character` feature (which implies the `std` feature) to make it available.
 
 Every `BMXXX` has a `from` associated function to create the instance by a search pattern (the needle).
 
@@ -49,6 +49,18 @@ let bmb = BMByte::from("oocoo").unwrap();
 assert_eq!(vec![7, 1], bmb.rfind_in("coocoocoocoo", 2));
 ```
 
+If only one search direction is needed, use `BMByteForward` (`BMCharacterForward`) or `BMByteReverse` (`BMCharacterReverse`) instead. They only build the bad character shift map for their own direction.
+
+```rust
+use boyer_moore_magiclen::{BMByteForward, BMByteReverse};
+
+let bmb_forward = BMByteForward::from("oocoo").unwrap();
+let bmb_reverse = BMByteReverse::from("oocoo").unwrap();
+
+assert_eq!(vec![1, 7], bmb_forward.find_in("coocoocoocoo", 2));
+assert_eq!(vec![7, 1], bmb_reverse.rfind_in("coocoocoocoo", 2));
+```
+
 To search all results at a time, use the `find_all_in`, `rfind_all_in`, `find_full_all_in` or `rfind_full_all_in` method.
 
 ```rust
@@ -58,22 +70,315 @@ let bmb = BMByte::from("oocoo").unwrap();
 
 assert_eq!(vec![7, 4, 1], bmb.rfind_full_all_in("coocoocoocoo"));
 ```
+
+To create a `BMByte` instance with options, use the `builder` associated function. For example, the scanning algorithm can be switched to Boyer-Moore-Horspool, Sunday (Quick Search), Raita, Tuned Boyer-Moore, Shift-Or or BNDM, the good suffix rule or a bigram (two-byte) shift map for long patterns over small alphabets can be enabled, and the linear worst-case time complexity can be guaranteed for untrusted needles and haystacks. If no algorithm is set, the bit-parallel Shift-Or is chosen for patterns which are not longer than 8 bytes, and Boyer-Moore-MagicLen for the others.
+
+```rust
+use boyer_moore_magiclen::{Algorithm, BMByte};
+
+let bmb = BMByte::builder("oocoo").algorithm(Algorithm::Sunday).linear(true).build().unwrap();
+
+assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
+```
+
+To search case-insensitively for ASCII (e.g. HTTP headers or SQL keywords), enable the `ignore_ascii_case` option. The shift maps are built over the pattern in lowercase and the text is compared in lowercase, for every algorithm and every search method.
+
+```rust
+use boyer_moore_magiclen::BMByte;
+
+let bmb = BMByte::builder("select").ignore_ascii_case(true).build().unwrap();
+
+assert_eq!(vec![0, 13], bmb.find_all_in("SELECT 1; -- Select 2"));
+```
+
+For `BMCharacter`, the `ignore_case` option ignores the case by the Unicode simple case folding, and the `full_case_folding` option uses the full case folding (e.g. `ß` matches `ss`). The positions are reported in the original character sequence.
+
+*/
+#![cfg_attr(
+    feature = "character",
+    doc = r#"
+```rust
+use boyer_moore_magiclen::BMCharacter;
+
+let bmc = BMCharacter::builder(vec!['s', 't', 'r', 'a', 's', 's', 'e']).full_case_folding(true).build().unwrap();
+
+assert_eq!(vec![0, 7], bmc.find_all_in("Straße STRASSE".chars().collect::<Vec<char>>()));
+```
+"#
+)]
+/*!
+
+If the length of the pattern is known at compile time (from 1 to 32 bytes), `BMByteN` holds the pattern and the shift maps in arrays instead of the heap and gives the same results as `BMByte`.
+
+```rust
+use boyer_moore_magiclen::BMByteN;
+
+let bmb = BMByteN::<5>::from("oocoo").unwrap();
+
+assert_eq!(vec![1, 7], bmb.find_all_in("coocoocoocoo"));
+```
+
+To search many patterns at once, use the `BMByteSet` struct. It uses the Wu-Manber algorithm, which shares one shift table among all patterns, and reports the IDs of the patterns (their indices) with the positions. The matches can be chosen by `MatchKind::LeftmostFirst` (the default), `MatchKind::LeftmostLongest` or `MatchKind::All` (including the overlapping ones).
+
+```rust
+use boyer_moore_magiclen::{BMByteSet, MatchKind};
+
+let set = BMByteSet::builder(["cat", "oocoo", "oocoocat"])
+    .match_kind(MatchKind::LeftmostLongest)
+    .build()
+    .unwrap();
+
+assert_eq!(vec![(2, 1), (1, 9)], set.find_all_in("coocoocatoocoo"));
+```
+
+To replace many needles at once, use the `BMByteSet::replace_all` associated function. The needles are replaced simultaneously in a single scan (leftmost-longest), so a replacement is never matched by another needle.
+
+```rust
+use boyer_moore_magiclen::BMByteSet;
+
+let result = BMByteSet::replace_all("cat dog catalog", &[("cat", "dog"), ("dog", "cat"), ("catalog", "list")]);
+
+assert_eq!(b"dog cat list", result.as_slice());
+```
+
+The `byte::SignatureScanner` struct is built on `BMByteSet` to find embedded files by their signatures (the magic bytes, optionally at a fixed offset from the start of the file, and optionally a trailer marking the end of the file), e.g. to carve files out of disk images and firmware blobs. A built-in table for PNG, JPEG, ZIP, ELF, PDF and gzip is available as `SignatureScanner::builtin`. With the `std` feature, the `scan_reader` method scans any `std::io::Read` stream.
+
+```rust
+use boyer_moore_magiclen::byte::{FileFormat, SignatureHit, SignatureScanner};
+
+let scanner = SignatureScanner::builtin();
+
+let hits = scanner.scan(b"junk%PDF-1.7 ...%%EOF".as_slice());
+
+assert_eq!(vec![SignatureHit { kind: FileFormat::Pdf, start: 4, end: Some(21) }], hits);
+```
+
+To search a large text (e.g. a multi-gigabyte buffer) by multiple threads, enable the `rayon` feature and use the `par_find_all_in`, `par_find_full_all_in` or `par_count_in` method. The results are the same as searching by one thread.
+
+*/
+#![cfg_attr(
+    feature = "rayon",
+    doc = r#"
+```rust
+use boyer_moore_magiclen::BMByte;
+
+let bmb = BMByte::from("oocoo").unwrap();
+
+assert_eq!(vec![1, 7], bmb.par_find_all_in("coocoocoocoo"));
+```
+"#
+)]
+/*!
+
+To search any `std::io::Read` stream (e.g. a file or a socket) without loading the whole stream into memory, enable the `std` feature and use the `StreamSearcher` struct. It reads the stream in fixed-size buffers and yields the absolute positions as `u64`, including the matches straddling two buffers.
+
+*/
+#![cfg_attr(
+    feature = "std",
+    doc = r#"
+```rust
+use boyer_moore_magiclen::{BMByte, StreamSearcher};
+
+let bmb = BMByte::from("oocoo").unwrap();
+
+let reader = "coocoocoocoo".as_bytes();
+
+let result: Vec<u64> = StreamSearcher::new(&bmb, reader).map(|r| r.unwrap()).collect();
+
+assert_eq!(vec![1, 7], result);
+```
+"#
+)]
+/*!
+
+With the `tokio` feature, the `AsyncStreamSearcher` struct does the same for any `tokio::io::AsyncRead` stream and yields the positions as a `futures_core::Stream`. The new bytes are searched as soon as they are read.
+
+To find the last matches in a seekable stream (e.g. the last `startxref` in a huge PDF file), use the `ReverseStreamSearcher` struct. It reads the stream backward from its end and yields the positions from the tail to the head.
+
+*/
+#![cfg_attr(
+    feature = "std",
+    doc = r#"
+```rust
+use std::io::Cursor;
+
+use boyer_moore_magiclen::{BMByte, ReverseStreamSearcher};
+
+let bmb = BMByte::from("startxref").unwrap();
+
+let reader = Cursor::new(b"startxref\n0\n%%EOF\nstartxref\n9\n%%EOF\n");
+
+assert_eq!(18, ReverseStreamSearcher::new(&bmb, reader).next().unwrap().unwrap());
+```
+"#
+)]
+/*!
+
+The `BMByteBufReadExt` trait extends any `std::io::BufRead` with the `read_until_pattern` method, which is like `read_until` but accepts a multi-byte delimiter, and the `records` method, which splits the stream into records separated by a multi-byte delimiter.
+
+*/
+#![cfg_attr(
+    feature = "std",
+    doc = r#"
+```rust
+use boyer_moore_magiclen::{BMByte, BMByteBufReadExt};
+
+let bmb = BMByte::from("\r\n\r\n").unwrap();
+
+let reader = "a\r\n\r\nb\r\nc".as_bytes();
+
+let records: Vec<Vec<u8>> = reader.records(&bmb).map(|r| r.unwrap()).collect();
+
+assert_eq!(vec![b"a".to_vec(), b"b\r\nc".to_vec()], records);
+```
+"#
+)]
+/*!
+
+To replace a pattern in the bytes flowing through a `std::io::Write` (e.g. to redact secrets from the output of a process), use the `ReplaceWriter` struct. Only the bytes which may be the head of an occurrence are held back between the `write` calls.
+
+*/
+#![cfg_attr(
+    feature = "std",
+    doc = r#"
+```rust
+use std::io::Write;
+
+use boyer_moore_magiclen::{BMByte, ReplaceWriter};
+
+let bmb = BMByte::from("secret").unwrap();
+
+let mut writer = ReplaceWriter::new(&bmb, "******", Vec::new());
+
+writer.write_all(b"password: sec").unwrap();
+writer.write_all(b"ret").unwrap();
+
+assert_eq!(b"password: ******", writer.into_inner().unwrap().as_slice());
+```
+"#
+)]
+/*!
+
+With the `tokio-util` feature, the `BMByteCodec` struct implements `Decoder` and `Encoder` of `tokio_util::codec` to frame a byte stream on a multi-byte delimiter, optionally with a maximum frame length.
+
+*/
+#![cfg_attr(
+    feature = "tokio-util",
+    doc = r#"
+```rust
+use boyer_moore_magiclen::{BMByte, BMByteCodec};
+use bytes::BytesMut;
+use tokio_util::codec::Decoder;
+
+let mut codec = BMByteCodec::new_with_max_length(BMByte::from("\r\n\r\n").unwrap(), 8 * 1024);
+
+let mut buf = BytesMut::from(&b"a\r\n\r\nb"[..]);
+
+assert_eq!(&b"a"[..], codec.decode(&mut buf).unwrap().unwrap());
+assert_eq!(None, codec.decode(&mut buf).unwrap());
+```
+"#
+)]
+/*!
+
+With the `nom` feature, the `take_until_bm` and `take_until_bm_rev` parsers in the `byte::nom::complete` and `byte::nom::streaming` modules take the input until the first or the last occurrence of a pattern, like `nom`'s `take_until` but using Boyer-Moore-MagicLen. The streaming parsers return `Incomplete` with the minimum number of bytes needed for the pattern to occur.
+
 */
+#![cfg_attr(
+    feature = "nom",
+    doc = r#"
+```rust
+use boyer_moore_magiclen::{byte::nom::complete::take_until_bm, BMByte};
+use nom::IResult;
+
+let bmb = BMByte::from("eof").unwrap();
+
+let result: IResult<&str, &str> = take_until_bm(&bmb)("hello, worldeof");
+
+assert_eq!(Ok(("eof", "hello, world")), result);
+```
+"#
+)]
+/*!
+
+With the `flate2` or `zstd` feature, the `find_in_compressed_file` method searches a file which may be compressed by gzip or zstd, detecting the compression from the magic bytes and reporting the positions in the decompressed bytes. The `byte::decompressing_reader` function wraps any `std::io::Read` in the same way, so it can be searched by `StreamSearcher`.
+
+*/
+#![cfg_attr(
+    any(feature = "flate2", feature = "zstd"),
+    doc = r#"
+```rust,no_run
+use boyer_moore_magiclen::BMByte;
+
+let bmb = BMByte::from("ERROR").unwrap();
+
+let result = bmb.find_in_compressed_file("/var/log/syslog.2.gz", 0).unwrap();
+```
+"#
+)]
+/*!
 
-#![cfg_attr(not(feature = "character"), no_std)]
+With the `mmap` feature, the `find_in_file`, `rfind_in_file` and `count_in_file` methods search a file by mapping it into memory, and return the positions as `u64`. The files which cannot be mapped (e.g. pipes) are read as streams instead.
+
+*/
+#![cfg_attr(
+    feature = "mmap",
+    doc = r#"
+```rust,no_run
+use boyer_moore_magiclen::BMByte;
+
+let bmb = BMByte::from("oocoo").unwrap();
+
+let result = bmb.find_in_file("/path/to/file", 0).unwrap();
+```
+"#
+)]
+/*!
+
+When the bytes arrive piece by piece and cannot be read from a `Read` (e.g. in a protocol handler), use the `IncrementalMatcher` struct. It remembers the partial match at the end of the fed bytes, so the matches straddling two pieces are found without scanning the consumed bytes again.
+
+```rust
+use boyer_moore_magiclen::{BMByte, IncrementalMatcher};
+
+let bmb = BMByte::from("\r\n\r\n").unwrap();
+
+let mut matcher = IncrementalMatcher::new(&bmb);
+
+assert_eq!(None, matcher.feed(b"GET / HTTP/1.1\r\nHost: a\r\n\r").next());
+assert_eq!(Some(23), matcher.feed(b"\nbody").next());
+```
+*/
+
+#![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 #[macro_use]
 extern crate alloc;
 
+mod bit_parallel;
 /// This module helps you search sub-sequences in any byte sequence, including self-synchronizing string encoding data such as UTF-8.
 pub mod byte;
 #[cfg(feature = "character")]
 /// This module helps you search character sub-sequences in any character sequence.
 pub mod character;
+mod good_suffix;
+mod two_way;
 
-pub use byte::{BMByte, BMByteBadCharShiftMap, BMByteBadCharShiftMapRev, BMByteSearchable};
+#[cfg(feature = "tokio")]
+pub use byte::AsyncStreamSearcher;
+pub use byte::{
+    Algorithm, BMByte, BMByteBadCharShiftMap, BMByteBadCharShiftMapRev, BMByteBigramShiftMap,
+    BMByteBigramShiftMapRev, BMByteBuilder, BMByteForward, BMByteGoodSuffixShiftMap,
+    BMByteGoodSuffixShiftMapRev, BMByteN, BMByteReverse, BMByteSearchable, BMByteSet,
+    BMByteSetBuilder, BMByteTuning, BMByteTuningReason, IncrementalMatcher, MatchKind,
+};
+#[cfg(feature = "std")]
+pub use byte::{BMByteBufReadExt, Records, ReplaceWriter, ReverseStreamSearcher, StreamSearcher};
+#[cfg(feature = "tokio-util")]
+pub use byte::{BMByteCodec, BMByteCodecError};
 #[cfg(feature = "character")]
 pub use character::{
-    BMCharacter, BMCharacterBadCharShiftMap, BMCharacterBadCharShiftMapRev, BMCharacterSearchable,
+    BMCharacter, BMCharacterBadCharShiftMap, BMCharacterBadCharShiftMapRev, BMCharacterBuilder,
+    BMCharacterForward, BMCharacterGoodSuffixShiftMap, BMCharacterGoodSuffixShiftMapRev,
+    BMCharacterReverse, BMCharacterSearchable,
 };
@@ -0,0 +1,138 @@
+use alloc::vec::Vec;
+
+/// The critical factorization of a pattern used by the Two-Way algorithm (Crochemore-Perrin), which finds all occurrences in linear time with constant extra space.
+#[derive(Debug, Clone)]
+pub(crate) struct TwoWay {
+    ell:      isize,
+    per:      usize,
+    periodic: bool,
+}
+
+/// Compute the maximal suffix of the pattern and its period. If `tilde` is `true`, the reversed alphabet order is used.
+fn max_suf<T: Ord>(m: usize, at: &impl Fn(usize) -> T, tilde: bool) -> (isize, usize) {
+    let m = m as isize;
+
+    let mut ms = -1isize;
+    let mut j = 0isize;
+    let mut k = 1isize;
+    let mut p = 1isize;
+
+    while j + k < m {
+        let a = at((j + k) as usize);
+        let b = at((ms + k) as usize);
+
+        let (less, greater) = if tilde { (a > b, a < b) } else { (a < b, a > b) };
+
+        if less {
+            j += k;
+            k = 1;
+            p = j - ms;
+        } else if greater {
+            ms = j;
+            j = ms + 1;
+            k = 1;
+            p = 1;
+        } else if k != p {
+            k += 1;
+        } else {
+            j += p;
+            k = 1;
+        }
+    }
+
+    (ms, p as usize)
+}
+
+impl TwoWay {
+    /// Compute the critical factorization of a pattern. `m` must not be `0`.
+    pub(crate) fn new<T: Ord>(m: usize, at: impl Fn(usize) -> T) -> TwoWay {
+        let (ms1, p1) = max_suf(m, &at, false);
+        let (ms2, p2) = max_suf(m, &at, true);
+
+        let (ell, per) = if ms1 > ms2 { (ms1, p1) } else { (ms2, p2) };
+
+        let periodic = ell + (per as isize) < m as isize
+            && (0..(ell + 1) as usize).all(|i| at(i) == at(i + per));
+
+        if periodic {
+            TwoWay {
+                ell,
+                per,
+                periodic,
+            }
+        } else {
+            TwoWay {
+                ell,
+                per: (ell + 1).max(m as isize - ell - 1) as usize + 1,
+                periodic,
+            }
+        }
+    }
+
+    /// Find the positions of the pattern in the text from the position `start`, and push them into `result` until its length reaches `limit` (`0` means no limit). If `full` is `false`, the overlapping occurrences are skipped.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn find<T: PartialEq>(
+        &self,
+        text_len: usize,
+        text_at: impl Fn(usize) -> T,
+        pattern_len: usize,
+        pattern_at: impl Fn(usize) -> T,
+        start: usize,
+        limit: usize,
+        full: bool,
+        result: &mut Vec<usize>,
+    ) {
+        if text_len < pattern_len {
+            return;
+        }
+
+        let m = pattern_len as isize;
+        let ell = self.ell;
+        let end_index = text_len - pattern_len;
+
+        let mut j = start;
+        let mut memory = -1isize;
+
+        while j <= end_index {
+            let mut i = ell.max(memory) + 1;
+
+            while i < m && pattern_at(i as usize) == text_at(i as usize + j) {
+                i += 1;
+            }
+
+            if i >= m {
+                let lower = if self.periodic { memory } else { -1 };
+
+                i = ell;
+
+                while i > lower && pattern_at(i as usize) == text_at(i as usize + j) {
+                    i -= 1;
+                }
+
+                if i <= lower {
+                    result.push(j);
+
+                    if result.len() == limit {
+                        return;
+                    }
+
+                    if !full {
+                        j += pattern_len;
+                        memory = -1;
+
+                        continue;
+                    }
+                }
+
+                j += self.per;
+
+                if self.periodic {
+                    memory = m - self.per as isize - 1;
+                }
+            } else {
+                j += (i - ell) as usize;
+                memory = -1;
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use boyer_moore_magiclen::*;
+
+const INPUT_DATA_PATH: &str = r"tests/data/utf8.txt";
+
+const ALGORITHMS: [Algorithm; 7] = [
+    Algorithm::MagicLen,
+    Algorithm::Horspool,
+    Algorithm::Sunday,
+    Algorithm::Raita,
+    Algorithm::TunedBoyerMoore,
+    Algorithm::ShiftOr,
+    Algorithm::Bndm,
+];
+
+#[test]
+fn data_input_from_file() {
+    common::data_input_from_file(
+        INPUT_DATA_PATH,
+        |text, pattern, answer, answer_not_full, answer_not_full_rev| {
+            for algorithm in ALGORITHMS {
+                for rev in [true, false] {
+                    let bm =
+                        BMByte::builder(pattern).rev(rev).algorithm(algorithm).build().unwrap();
+
+                    assert_eq!(answer, bm.find_full_all_in(text));
+                    assert_eq!(
+                        answer.iter().rev().copied().collect::<Vec<usize>>(),
+                        bm.rfind_full_all_in(text)
+                    );
+                    assert_eq!(answer_not_full, bm.find_all_in(text));
+                    assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+                }
+            }
+        },
+    );
+}
+
+#[test]
+fn repetitive() {
+    for algorithm in ALGORITHMS {
+        for linear in [false, true] {
+            common::check_against_naive(
+                common::repetitive_cases(),
+                &common::Search::ALL,
+                |pattern| {
+                    BMByte::builder(pattern).algorithm(algorithm).linear(linear).build().unwrap()
+                },
+                common::search_bmbyte,
+            );
+        }
+    }
+}
+
+#[test]
+fn linear_large() {
+    const N: usize = 1_000_000;
+    const M: usize = 1_000;
+
+    let text = vec![b'a'; N];
+
+    for algorithm in ALGORITHMS {
+        let bm = BMByte::builder(vec![b'a'; M]).algorithm(algorithm).linear(true).build().unwrap();
+
+        assert_eq!(N - M + 1, bm.find_full_all_in(&text).len());
+        assert_eq!(N - M + 1, bm.rfind_full_all_in(&text).len());
+    }
+}
+
+#[test]
+fn bit_parallel_word_size() {
+    let text = "ab".repeat(100);
+
+    for pattern_len in [1, 2, 8, 9, 63, 64, 65] {
+        let pattern = &text[..pattern_len];
+
+        let answer = common::naive_find_full(text.as_bytes(), pattern.as_bytes());
+
+        for algorithm in [Algorithm::ShiftOr, Algorithm::Bndm] {
+            let bm = BMByte::builder(pattern).algorithm(algorithm).build().unwrap();
+
+            if pattern_len > 64 {
+                assert_eq!(Algorithm::MagicLen, bm.algorithm());
+            } else {
+                assert_eq!(algorithm, bm.algorithm());
+            }
+
+            assert_eq!(answer, bm.find_full_all_in(text.as_str()));
+            assert_eq!(
+                answer.iter().rev().copied().collect::<Vec<usize>>(),
+                bm.rfind_full_all_in(text.as_str())
+            );
+            assert_eq!(
+                common::naive_find(text.as_bytes(), pattern.as_bytes()),
+                bm.find_all_in(text.as_str())
+            );
+            assert_eq!(
+                common::naive_rfind(text.as_bytes(), pattern.as_bytes()),
+                bm.rfind_all_in(text.as_str())
+            );
+        }
+    }
+
+    assert_eq!(Algorithm::ShiftOr, BMByte::from("abababab").unwrap().algorithm());
+    assert_eq!(Algorithm::MagicLen, BMByte::from("ababababa").unwrap().algorithm());
+    assert_eq!(
+        Algorithm::MagicLen,
+        BMByte::builder("abababab").linear(true).build().unwrap().algorithm()
+    );
+    assert_eq!(
+        Algorithm::MagicLen,
+        BMByte::builder("abababab").good_suffix(true).build().unwrap().algorithm()
+    );
+    assert_eq!(
+        Algorithm::MagicLen,
+        BMByte::builder("a".repeat(65)).algorithm(Algorithm::Bndm).build().unwrap().algorithm()
+    );
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "tokio")]
+
+mod common;
+
+use std::{
+    future::poll_fn,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use boyer_moore_magiclen::*;
+use futures_core::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// A reader which returns at most `step` bytes on each call and is pending before every read.
+struct ShortReader<'a> {
+    data:    &'a [u8],
+    step:    usize,
+    pending: bool,
+}
+
+impl AsyncRead for ShortReader<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        self.pending = !self.pending;
+
+        if self.pending {
+            cx.waker().wake_by_ref();
+
+            return Poll::Pending;
+        }
+
+        let len = self.step.min(buf.remaining()).min(self.data.len());
+
+        buf.put_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn collect<R: AsyncRead + Unpin>(mut searcher: AsyncStreamSearcher<'_, R>) -> Vec<usize> {
+    let mut result = Vec::new();
+
+    while let Some(position) = poll_fn(|cx| Pin::new(&mut searcher).poll_next(cx)).await {
+        result.push(position.unwrap() as usize);
+    }
+
+    result
+}
+
+#[tokio::test]
+async fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        let bm = BMByte::from(&pattern).unwrap();
+
+        let answer = common::naive_find_full(&text, &pattern);
+        let answer_not_full = common::naive_find(&text, &pattern);
+
+        for buffer_size in common::PIECE_LENS {
+            let searcher = AsyncStreamSearcher::new(&bm, text.as_slice()).buffer_size(buffer_size);
+
+            assert_eq!(answer_not_full, collect(searcher).await);
+
+            for step in [1, 2, 7] {
+                let reader = ShortReader {
+                    data: &text,
+                    step,
+                    pending: false,
+                };
+
+                let searcher =
+                    AsyncStreamSearcher::new(&bm, reader).full(true).buffer_size(buffer_size);
+
+                assert_eq!(answer, collect(searcher).await);
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn straddling() {
+    let bm = BMByte::from("\r\n\r\n").unwrap();
+
+    let text = b"GET / HTTP/1.1\r\nHost: a\r\n\r\nbody\r\n\r\n\r\n";
+
+    for step in 1..text.len() + 2 {
+        let reader = ShortReader {
+            data: text,
+            step,
+            pending: false,
+        };
+
+        assert_eq!(vec![23, 31], collect(AsyncStreamSearcher::new(&bm, reader)).await);
+    }
+}
+
+#[tokio::test]
+async fn error() {
+    struct FailingReader;
+
+    impl AsyncRead for FailingReader {
+        fn poll_read(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            _buf: &mut ReadBuf<'_>,
+        ) -> Poll<io::Result<()>> {
+            Poll::Ready(Err(io::Error::new(io::ErrorKind::Other, "broken")))
+        }
+    }
+
+    let bm = BMByte::from("oocoo").unwrap();
+
+    let mut searcher = AsyncStreamSearcher::new(&bm, FailingReader);
+
+    assert!(poll_fn(|cx| Pin::new(&mut searcher).poll_next(cx)).await.unwrap().is_err());
+    assert!(poll_fn(|cx| Pin::new(&mut searcher).poll_next(cx)).await.is_none());
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use boyer_moore_magiclen::*;
+
+const INPUT_DATA_PATH: &str = r"tests/data/utf8.txt";
+
+#[test]
+fn data_input_from_file() {
+    common::data_input_from_file(
+        INPUT_DATA_PATH,
+        |text, pattern, answer, answer_not_full, answer_not_full_rev| {
+            for rev in [true, false] {
+                let bm = BMByte::builder(pattern).rev(rev).bigram(true).build().unwrap();
+
+                assert_eq!(answer, bm.find_full_all_in(text));
+                assert_eq!(
+                    answer.iter().rev().copied().collect::<Vec<usize>>(),
+                    bm.rfind_full_all_in(text)
+                );
+                assert_eq!(answer_not_full, bm.find_all_in(text));
+                assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+            }
+        },
+    );
+}
+
+/// The shift maps of a pattern for the bigram functions.
+struct BigramShiftMaps {
+    pattern:                Vec<u8>,
+    bad_char_shift_map:     BMByteBadCharShiftMap,
+    bad_char_shift_map_rev: BMByteBadCharShiftMapRev,
+    bigram_shift_map:       BMByteBigramShiftMap,
+    bigram_shift_map_rev:   BMByteBigramShiftMapRev,
+}
+
+#[test]
+fn repetitive() {
+    let cases = common::repetitive_cases().into_iter().filter(|(_, pattern)| pattern.len() >= 2);
+
+    common::check_against_naive(
+        cases,
+        &common::Search::ALL,
+        |pattern| BigramShiftMaps {
+            pattern:                pattern.to_vec(),
+            bad_char_shift_map:     BMByteBadCharShiftMap::create_bad_char_shift_map(pattern)
+                .unwrap(),
+            bad_char_shift_map_rev: BMByteBadCharShiftMapRev::create_bad_char_shift_map(pattern)
+                .unwrap(),
+            bigram_shift_map:       BMByteBigramShiftMap::create_bigram_shift_map(pattern).unwrap(),
+            bigram_shift_map_rev:   BMByteBigramShiftMapRev::create_bigram_shift_map(pattern)
+                .unwrap(),
+        },
+        |maps, text, kind, limit| {
+            let full = matches!(kind, common::Search::FindFull | common::Search::RFindFull);
+
+            match kind {
+                common::Search::FindFull | common::Search::Find => byte::find_with_bigram(
+                    text,
+                    &maps.pattern,
+                    &maps.bad_char_shift_map,
+                    &maps.bigram_shift_map,
+                    limit,
+                    full,
+                ),
+                common::Search::RFindFull | common::Search::RFind => byte::rfind_with_bigram(
+                    text,
+                    &maps.pattern,
+                    &maps.bad_char_shift_map_rev,
+                    &maps.bigram_shift_map_rev,
+                    limit,
+                    full,
+                ),
+            }
+        },
+    );
+}
+
+/// Make the patterns long enough for the bigram shift maps to be used.
+fn long_cases() -> impl Iterator<Item = (Vec<u8>, Vec<u8>)> {
+    common::repetitive_cases().into_iter().map(|(text, pattern)| {
+        let pattern: Vec<u8> = pattern.iter().copied().cycle().take(pattern.len() + 16).collect();
+
+        let mut text = text.repeat(3);
+        text.extend_from_slice(&pattern);
+        text.extend_from_slice(&pattern[1..]);
+
+        (text, pattern)
+    })
+}
+
+#[test]
+fn repetitive_long() {
+    for (good_suffix, linear) in [(false, false), (true, false), (false, true), (true, true)] {
+        for rev in [true, false] {
+            common::check_against_naive(
+                long_cases(),
+                &common::Search::ALL,
+                |pattern| {
+                    let bm = BMByte::builder(pattern)
+                        .rev(rev)
+                        .bigram(true)
+                        .good_suffix(good_suffix)
+                        .linear(linear)
+                        .build()
+                        .unwrap();
+
+                    assert_eq!(Algorithm::MagicLen, bm.algorithm());
+
+                    bm
+                },
+                common::search_bmbyte,
+            );
+        }
+    }
+}
+
+#[test]
+fn adversarial_linear() {
+    const N: usize = 1_000_000;
+    const M: usize = 1_000;
+
+    let text = vec![b'a'; N];
+
+    let mut pattern = vec![b'a'; M];
+    pattern[0] = b'b';
+
+    let bm = BMByte::builder(&pattern).bigram(true).linear(true).build().unwrap();
+
+    assert!(bm.find_all_in(&text).is_empty());
+    assert!(bm.rfind_all_in(&text).is_empty());
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::io::{BufRead, BufReader};
+
+use boyer_moore_magiclen::*;
+
+#[test]
+fn repetitive() {
+    for capacity in common::PIECE_LENS {
+        common::check_against_naive(
+            common::repetitive_cases(),
+            &[common::Search::Find],
+            |pattern| BMByte::from(pattern).unwrap(),
+            |bm, text, _, limit| {
+                // the delimiters are found right after the records
+                let mut result = Vec::new();
+                let mut start = 0;
+
+                for record in BufReader::with_capacity(capacity, text).records(bm) {
+                    let record = record.unwrap();
+
+                    assert!(start < text.len());
+                    assert_eq!(&text[start..start + record.len()], record.as_slice());
+
+                    start += record.len();
+
+                    if start < text.len() {
+                        result.push(start);
+
+                        start += bm.pattern().len();
+                    }
+                }
+
+                if limit > 0 {
+                    result.truncate(limit);
+                }
+
+                result
+            },
+        );
+    }
+}
+
+#[test]
+fn read_until_pattern() {
+    let bm = BMByte::from("\r\n\r\n").unwrap();
+
+    let text = b"GET / HTTP/1.1\r\nHost: a\r\n\r\nbody\r\n\r\r\n\r\n";
+
+    for capacity in 1..text.len() + 2 {
+        let mut reader = BufReader::with_capacity(capacity, &text[..]);
+
+        // the bytes which are already in `buf` are kept and not searched
+        let mut buf = b"\r\n\r".to_vec();
+
+        assert_eq!(27, reader.read_until_pattern(&bm, &mut buf).unwrap());
+        assert_eq!(b"\r\n\rGET / HTTP/1.1\r\nHost: a\r\n\r\n", buf.as_slice());
+
+        buf.clear();
+
+        assert_eq!(11, reader.read_until_pattern(&bm, &mut buf).unwrap());
+        assert_eq!(b"body\r\n\r\r\n\r\n", buf.as_slice());
+
+        buf.clear();
+
+        assert_eq!(0, reader.read_until_pattern(&bm, &mut buf).unwrap());
+        assert!(reader.fill_buf().unwrap().is_empty());
+    }
+}
@@ -14,7 +14,7 @@ fn data_input_from_file() {
             let pattern = pattern.chars().collect::<Vec<char>>();
             let text = text.chars().collect::<Vec<char>>();
 
-            let bm = BMCharacter::from(pattern).unwrap();
+            let bm = BMCharacter::from(&pattern).unwrap();
 
             assert_eq!(answer, bm.find_full_all_in(&text));
             assert_eq!(
@@ -23,6 +23,67 @@ fn data_input_from_file() {
             );
             assert_eq!(answer_not_full, bm.find_all_in(&text));
             assert_eq!(answer_not_full_rev, bm.rfind_all_in(&text));
+
+            let bm = BMCharacter::from_with_rev(&pattern, false).unwrap();
+
+            assert_eq!(
+                answer.iter().rev().copied().collect::<Vec<usize>>(),
+                bm.rfind_full_all_in(&text)
+            );
+            assert_eq!(answer_not_full_rev, bm.rfind_all_in(&text));
+
+            let bm_forward = BMCharacterForward::from(&pattern).unwrap();
+
+            assert_eq!(answer, bm_forward.find_full_all_in(&text));
+            assert_eq!(answer_not_full, bm_forward.find_all_in(&text));
+
+            let bm_reverse = BMCharacterReverse::from(&pattern).unwrap();
+
+            assert_eq!(
+                answer.iter().rev().copied().collect::<Vec<usize>>(),
+                bm_reverse.rfind_full_all_in(&text)
+            );
+            assert_eq!(answer_not_full_rev, bm_reverse.rfind_all_in(&text));
+
+            for rev in [true, false] {
+                let bm = BMCharacter::builder(&pattern).rev(rev).good_suffix(true).build().unwrap();
+
+                assert_eq!(answer, bm.find_full_all_in(&text));
+                assert_eq!(
+                    answer.iter().rev().copied().collect::<Vec<usize>>(),
+                    bm.rfind_full_all_in(&text)
+                );
+                assert_eq!(answer_not_full, bm.find_all_in(&text));
+                assert_eq!(answer_not_full_rev, bm.rfind_all_in(&text));
+            }
         },
     );
 }
+
+#[test]
+fn good_suffix_repetitive() {
+    // the patterns of up to 8 characters are searched by BNDM unless the good suffix rule is enabled
+    for good_suffix in [false, true] {
+        for rev in [true, false] {
+            let cases = common::repetitive_cases().into_iter().map(|(text, pattern)| {
+                (
+                    text.into_iter().map(char::from).collect::<Vec<char>>(),
+                    pattern.into_iter().map(char::from).collect::<Vec<char>>(),
+                )
+            });
+
+            common::check_against_naive(
+                cases,
+                &common::Search::ALL,
+                |pattern| {
+                    BMCharacter::builder(pattern.to_vec())
+                        .rev(rev)
+                        .good_suffix(good_suffix)
+                        .build()
+                        .unwrap()
+                },
+                common::search_bmcharacter,
+            );
+        }
+    }
+}
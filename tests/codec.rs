@@ -0,0 +1,130 @@
+#![cfg(feature = "tokio-util")]
+
+mod common;
+
+use boyer_moore_magiclen::*;
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+fn decode(codec: &mut BMByteCodec, text: &[u8], piece_len: usize) -> Vec<Vec<u8>> {
+    let mut buf = BytesMut::new();
+    let mut result = Vec::new();
+
+    for piece in text.chunks(piece_len) {
+        buf.extend_from_slice(piece);
+
+        while let Some(frame) = codec.decode(&mut buf).unwrap() {
+            result.push(frame.to_vec());
+        }
+    }
+
+    while let Some(frame) = codec.decode_eof(&mut buf).unwrap() {
+        result.push(frame.to_vec());
+    }
+
+    result
+}
+
+#[test]
+fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        let answer = common::naive_split(&text, &pattern);
+
+        for piece_len in common::PIECE_LENS {
+            let mut codec = BMByteCodec::new(BMByte::from(&pattern).unwrap());
+
+            assert_eq!(answer, decode(&mut codec, &text, piece_len));
+        }
+    }
+}
+
+#[test]
+fn max_length() {
+    let text = b"abc\r\n\r\nabcd\r\n\r\nabcdefgh\r\n\r\n\r\nab\r\n\r\nabcdefgh";
+
+    for piece_len in 1..text.len() + 2 {
+        let mut codec = BMByteCodec::new_with_max_length(BMByte::from("\r\n\r\n").unwrap(), 4);
+
+        let mut buf = BytesMut::new();
+        let mut result = Vec::new();
+
+        for piece in text.chunks(piece_len) {
+            buf.extend_from_slice(piece);
+
+            loop {
+                match codec.decode(&mut buf) {
+                    Ok(Some(frame)) => result.push(Ok(frame.to_vec())),
+                    Ok(None) => break,
+                    Err(BMByteCodecError::MaxFrameLengthExceeded) => result.push(Err(())),
+                    Err(err) => panic!("{err}"),
+                }
+            }
+        }
+
+        loop {
+            match codec.decode_eof(&mut buf) {
+                Ok(Some(frame)) => result.push(Ok(frame.to_vec())),
+                Ok(None) => break,
+                Err(BMByteCodecError::MaxFrameLengthExceeded) => result.push(Err(())),
+                Err(err) => panic!("{err}"),
+            }
+        }
+
+        assert_eq!(
+            vec![
+                Ok(b"abc".to_vec()),
+                Ok(b"abcd".to_vec()),
+                Err(()),
+                Ok(b"\r\nab".to_vec()),
+                Err(())
+            ],
+            result
+        );
+    }
+
+    // the bytes left at the end of the stream are a frame too
+    for (text, answer) in [(&b"abcd"[..], Ok(b"abcd".to_vec())), (b"abcdef", Err(()))] {
+        let mut codec = BMByteCodec::new_with_max_length(BMByte::from("\r\n\r\n").unwrap(), 4);
+
+        let mut buf = BytesMut::from(text);
+
+        assert_eq!(None, codec.decode(&mut buf).unwrap());
+
+        match codec.decode_eof(&mut buf) {
+            Ok(Some(frame)) => assert_eq!(answer, Ok(frame.to_vec())),
+            Err(BMByteCodecError::MaxFrameLengthExceeded) => assert_eq!(answer, Err(())),
+            result => panic!("{result:?}"),
+        }
+
+        assert_eq!(None, codec.decode_eof(&mut buf).unwrap());
+    }
+}
+
+#[test]
+fn encode() {
+    let mut codec = BMByteCodec::new(BMByte::from("\r\n\r\n").unwrap());
+
+    let mut buf = BytesMut::new();
+
+    codec.encode("a", &mut buf).unwrap();
+    codec.encode(b"b\r\nc", &mut buf).unwrap();
+
+    assert_eq!(&b"a\r\n\r\nb\r\nc\r\n\r\n"[..], buf);
+
+    assert_eq!(&b"a"[..], codec.decode(&mut buf).unwrap().unwrap());
+    assert_eq!(&b"b\r\nc"[..], codec.decode(&mut buf).unwrap().unwrap());
+    assert_eq!(None, codec.decode_eof(&mut buf).unwrap());
+
+    // the delimiter is encoded as it was given even if its case is ignored
+    let mut codec =
+        BMByteCodec::new(BMByte::builder("\r\nEND\r\n").ignore_ascii_case(true).build().unwrap());
+
+    codec.encode("a", &mut buf).unwrap();
+
+    assert_eq!(&b"a\r\nEND\r\n"[..], buf);
+
+    buf.extend_from_slice(b"b\r\nend\r\n");
+
+    assert_eq!(&b"a"[..], codec.decode(&mut buf).unwrap().unwrap());
+    assert_eq!(&b"b"[..], codec.decode(&mut buf).unwrap().unwrap());
+}
@@ -1,5 +1,6 @@
-use std::path::Path;
+use std::{fmt::Debug, path::Path, slice::Iter};
 
+use boyer_moore_magiclen::{BMByte, BMByteSearchable};
 use scanner_rust::Scanner;
 
 #[allow(dead_code)]
@@ -68,3 +69,212 @@ pub(crate) fn data_input_from_file<
         }
     }
 }
+
+#[allow(dead_code)]
+pub(crate) fn naive_find_full<T: PartialEq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    if pattern.is_empty() || text.len() < pattern.len() {
+        return vec![];
+    }
+
+    (0..=text.len() - pattern.len()).filter(|&i| &text[i..i + pattern.len()] == pattern).collect()
+}
+
+#[allow(dead_code)]
+pub(crate) fn naive_find<T: PartialEq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    let mut result = vec![];
+
+    let mut min_index = 0;
+
+    for index in naive_find_full(text, pattern) {
+        if index >= min_index {
+            result.push(index);
+
+            min_index = index + pattern.len();
+        }
+    }
+
+    result
+}
+
+#[allow(dead_code)]
+pub(crate) fn naive_rfind<T: PartialEq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    let mut result = vec![];
+
+    let mut max_index = usize::MAX;
+
+    for index in naive_find_full(text, pattern).into_iter().rev() {
+        if index + pattern.len() <= max_index {
+            result.push(index);
+
+            max_index = index;
+        }
+    }
+
+    result
+}
+
+/// Generate repetitive texts and patterns over a small alphabet with a simple linear congruential generator.
+#[allow(dead_code)]
+pub(crate) fn repetitive_cases() -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut seed = 0x2545_F491u32;
+
+    let mut next = move |n: u32| {
+        seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+
+        (seed >> 16) % n
+    };
+
+    let mut cases = vec![
+        (b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaab".to_vec(), b"aaaaab".to_vec()),
+        (b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa".to_vec(), b"aaaa".to_vec()),
+        (b"coocoocoocoocoocoocoo".to_vec(), b"oocoo".to_vec()),
+        (b"abababababababababab".to_vec(), b"abab".to_vec()),
+        (b"abaabaabaabaabaabaab".to_vec(), b"abaaba".to_vec()),
+    ];
+
+    for _ in 0..200 {
+        let alphabet = 1 + next(4) as u8;
+
+        let pattern_len = 1 + next(12) as usize;
+        let text_len = next(200) as usize;
+
+        let pattern: Vec<u8> =
+            (0..pattern_len).map(|_| b'a' + next(alphabet as u32) as u8).collect();
+        let text: Vec<u8> = (0..text_len).map(|_| b'a' + next(alphabet as u32) as u8).collect();
+
+        cases.push((text, pattern));
+    }
+
+    cases
+}
+
+/// A kind of search checked by `check_against_naive`.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Search {
+    /// Like `find_full_in`.
+    FindFull,
+    /// Like `rfind_full_in`.
+    RFindFull,
+    /// Like `find_in`.
+    Find,
+    /// Like `rfind_in`.
+    RFind,
+}
+
+#[allow(dead_code)]
+impl Search {
+    pub(crate) const ALL: [Search; 4] =
+        [Search::FindFull, Search::RFindFull, Search::Find, Search::RFind];
+}
+
+/// Check a searcher against the naive search on every case (a text and a pattern). `build` creates the searcher of a pattern, and `search` runs it on a text with a kind of search and a limit (`0` means no limit). Every kind in `kinds` is checked with no limit and with the limits from 1 to 3.
+#[allow(dead_code)]
+pub(crate) fn check_against_naive<T: PartialEq + Debug, S>(
+    cases: impl IntoIterator<Item = (Vec<T>, Vec<T>)>,
+    kinds: &[Search],
+    build: impl Fn(&[T]) -> S,
+    search: impl Fn(&S, &[T], Search, usize) -> Vec<usize>,
+) {
+    for (text, pattern) in cases {
+        let searcher = build(&pattern);
+
+        for &kind in kinds {
+            let answer = match kind {
+                Search::FindFull => naive_find_full(&text, &pattern),
+                Search::RFindFull => naive_find_full(&text, &pattern).into_iter().rev().collect(),
+                Search::Find => naive_find(&text, &pattern),
+                Search::RFind => naive_rfind(&text, &pattern),
+            };
+
+            for limit in 0..4 {
+                let answer =
+                    if limit == 0 { &answer[..] } else { &answer[..answer.len().min(limit)] };
+
+                assert_eq!(
+                    answer,
+                    search(&searcher, &text, kind, limit),
+                    "{kind:?} with the limit {limit} for {pattern:?} in {text:?}"
+                );
+            }
+        }
+    }
+}
+
+/// Run a `BMByte` instance for `check_against_naive`. The methods searching all matches are called if there is no limit.
+#[allow(dead_code)]
+pub(crate) fn search_bmbyte(bm: &BMByte, text: &[u8], kind: Search, limit: usize) -> Vec<usize> {
+    match (kind, limit) {
+        (Search::FindFull, 0) => bm.find_full_all_in(text),
+        (Search::FindFull, _) => bm.find_full_in(text, limit),
+        (Search::RFindFull, 0) => bm.rfind_full_all_in(text),
+        (Search::RFindFull, _) => bm.rfind_full_in(text, limit),
+        (Search::Find, 0) => bm.find_all_in(text),
+        (Search::Find, _) => bm.find_in(text, limit),
+        (Search::RFind, 0) => bm.rfind_all_in(text),
+        (Search::RFind, _) => bm.rfind_in(text, limit),
+    }
+}
+
+/// Run a `BMCharacter` instance for `check_against_naive`. The methods searching all matches are called if there is no limit.
+#[cfg(feature = "character")]
+#[allow(dead_code)]
+pub(crate) fn search_bmcharacter(
+    bm: &boyer_moore_magiclen::BMCharacter,
+    text: &[char],
+    kind: Search,
+    limit: usize,
+) -> Vec<usize> {
+    let text = text.to_vec();
+
+    match (kind, limit) {
+        (Search::FindFull, 0) => bm.find_full_all_in(&text),
+        (Search::FindFull, _) => bm.find_full_in(&text, limit),
+        (Search::RFindFull, 0) => bm.rfind_full_all_in(&text),
+        (Search::RFindFull, _) => bm.rfind_full_in(&text, limit),
+        (Search::Find, 0) => bm.find_all_in(&text),
+        (Search::Find, _) => bm.find_in(&text, limit),
+        (Search::RFind, 0) => bm.rfind_all_in(&text),
+        (Search::RFind, _) => bm.rfind_in(&text, limit),
+    }
+}
+
+/// The lengths of the pieces which the repetitive texts are fed in (or the buffer sizes they are read with), to make the matches straddle the pieces in every way.
+#[allow(dead_code)]
+pub(crate) const PIECE_LENS: [usize; 7] = [1, 2, 3, 5, 8, 13, 1000];
+
+/// Split the text on the non-overlapping occurrences of the pattern, like `BufRead::split` with a multi-byte delimiter. The bytes after the last occurrence are the last piece if they are not empty.
+#[allow(dead_code)]
+pub(crate) fn naive_split(text: &[u8], pattern: &[u8]) -> Vec<Vec<u8>> {
+    let mut result = Vec::new();
+    let mut start = 0;
+
+    for i in naive_find(text, pattern) {
+        result.push(text[start..i].to_vec());
+        start = i + pattern.len();
+    }
+
+    if start < text.len() {
+        result.push(text[start..].to_vec());
+    }
+
+    result
+}
+
+/// A text which can only be read by `len` and `value_at`. Its `iter` yields nothing, so the searchers must not read the text by `iter`.
+#[allow(dead_code)]
+pub(crate) struct ValueAtOnly(pub(crate) Vec<u8>);
+
+impl BMByteSearchable for ValueAtOnly {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn value_at(&self, index: usize) -> u8 {
+        self.0[index]
+    }
+
+    fn iter(&self) -> Iter<'_, u8> {
+        [].iter()
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(any(feature = "flate2", feature = "zstd"))]
+
+mod common;
+
+use std::{fs, io::Read, path::PathBuf};
+
+use boyer_moore_magiclen::{byte::decompressing_reader, *};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("boyer_moore_magiclen_test_{}_{}", std::process::id(), name))
+}
+
+/// Check that the compressed data are searched like the decompressed text.
+fn check(name: &str, compress: fn(&[u8]) -> Vec<u8>) {
+    let path = temp_path(name);
+
+    common::check_against_naive(
+        common::repetitive_cases(),
+        &[common::Search::Find],
+        |pattern| BMByte::from(pattern).unwrap(),
+        |bm, text, _, limit| {
+            fs::write(&path, compress(text)).unwrap();
+
+            bm.find_in_compressed_file(&path, limit)
+                .unwrap()
+                .into_iter()
+                .map(|i| i as usize)
+                .collect()
+        },
+    );
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn plain() {
+    check("plain", |text| text.to_vec());
+}
+
+#[test]
+fn short() {
+    for text in [&b""[..], b"\x1f", b"\x28\xb5\x2f"] {
+        let mut reader = decompressing_reader(text).unwrap();
+
+        let mut result = Vec::new();
+
+        reader.read_to_end(&mut result).unwrap();
+
+        assert_eq!(text, result);
+    }
+}
+
+#[cfg(feature = "flate2")]
+#[test]
+fn gzip() {
+    use std::io::Write;
+
+    use flate2::{write::GzEncoder, Compression};
+
+    fn gzip(text: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+
+        encoder.write_all(text).unwrap();
+
+        encoder.finish().unwrap()
+    }
+
+    check("gzip", gzip);
+
+    // multiple members
+    check("gzip_members", |text| {
+        let (a, b) = text.split_at(text.len() / 2);
+
+        let mut data = gzip(a);
+        data.extend(gzip(b));
+
+        data
+    });
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd() {
+    check("zstd", |text| zstd::encode_all(text, 0).unwrap());
+}
+
+#[cfg(not(feature = "zstd"))]
+#[test]
+fn unsupported() {
+    let result = decompressing_reader(&b"\x28\xb5\x2f\xfd\x00"[..]);
+
+    assert_eq!(std::io::ErrorKind::Unsupported, result.err().unwrap().kind());
+}
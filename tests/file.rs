@@ -0,0 +1,110 @@
+#![cfg(feature = "mmap")]
+
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use boyer_moore_magiclen::*;
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("boyer_moore_magiclen_test_{}_{}", std::process::id(), name))
+}
+
+#[test]
+fn repetitive() {
+    let path = temp_path("repetitive");
+
+    common::check_against_naive(
+        common::repetitive_cases(),
+        &[common::Search::Find, common::Search::RFind],
+        |pattern| BMByte::from(pattern).unwrap(),
+        |bm, text, kind, limit| {
+            fs::write(&path, text).unwrap();
+
+            let result = match kind {
+                common::Search::RFind => bm.rfind_in_file(&path, limit).unwrap(),
+                _ => bm.find_in_file(&path, limit).unwrap(),
+            };
+
+            result.into_iter().map(|i| i as usize).collect()
+        },
+    );
+
+    for (text, pattern) in common::repetitive_cases() {
+        fs::write(&path, &text).unwrap();
+
+        let bm = BMByte::from(&pattern).unwrap();
+
+        assert_eq!(
+            common::naive_find(&text, &pattern).len() as u64,
+            bm.count_in_file(&path).unwrap()
+        );
+    }
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn empty() {
+    let path = temp_path("empty");
+
+    fs::write(&path, b"").unwrap();
+
+    let bm = BMByte::from("oocoo").unwrap();
+
+    assert!(bm.find_in_file(&path, 0).unwrap().is_empty());
+    assert!(bm.rfind_in_file(&path, 0).unwrap().is_empty());
+    assert_eq!(0, bm.count_in_file(&path).unwrap());
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn not_found() {
+    let bm = BMByte::from("oocoo").unwrap();
+
+    assert!(bm.find_in_file(temp_path("not_found"), 0).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn fifo() {
+    use std::{
+        io::Write,
+        process::Command,
+        thread::{self, JoinHandle},
+    };
+
+    let path = temp_path("fifo");
+
+    assert!(Command::new("mkfifo").arg(&path).status().unwrap().success());
+
+    let write = || -> JoinHandle<()> {
+        let path = path.clone();
+
+        thread::spawn(move || {
+            fs::OpenOptions::new()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .write_all(b"coocoocoocoocoo")
+                .unwrap();
+        })
+    };
+
+    let bm = BMByte::from("oocoo").unwrap();
+
+    let writer = write();
+    assert_eq!(vec![1, 7], bm.find_in_file(&path, 0).unwrap());
+    writer.join().unwrap();
+
+    let writer = write();
+    assert_eq!(vec![10, 4], bm.rfind_in_file(&path, 0).unwrap());
+    writer.join().unwrap();
+
+    let writer = write();
+    assert_eq!(2, bm.count_in_file(&path).unwrap());
+    writer.join().unwrap();
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use boyer_moore_magiclen::*;
+
+const INPUT_DATA_PATH: &str = r"tests/data/utf8.txt";
+
+/// Check that `BMByteN<N>` gives the same results as `BMByte`.
+fn check<const N: usize>(text: &[u8], pattern: &[u8]) {
+    let text = &text.to_vec();
+
+    let bm = BMByte::from(pattern.to_vec()).unwrap();
+    let bm_n = BMByteN::<N>::from(pattern.to_vec()).unwrap();
+
+    assert_eq!(bm.find_full_all_in(text), bm_n.find_full_all_in(text));
+    assert_eq!(bm.rfind_full_all_in(text), bm_n.rfind_full_all_in(text));
+    assert_eq!(bm.find_all_in(text), bm_n.find_all_in(text));
+    assert_eq!(bm.rfind_all_in(text), bm_n.rfind_all_in(text));
+    assert_eq!(bm.find_first_in(text), bm_n.find_first_in(text));
+    assert_eq!(bm.rfind_first_in(text), bm_n.rfind_first_in(text));
+
+    for limit in 1..4 {
+        assert_eq!(bm.find_full_in(text, limit), bm_n.find_full_in(text, limit));
+        assert_eq!(bm.rfind_full_in(text, limit), bm_n.rfind_full_in(text, limit));
+        assert_eq!(bm.find_in(text, limit), bm_n.find_in(text, limit));
+        assert_eq!(bm.rfind_in(text, limit), bm_n.rfind_in(text, limit));
+    }
+}
+
+macro_rules! check_len {
+    ($text:expr, $pattern:expr, $($n:literal),*) => {
+        match $pattern.len() {
+            $($n => check::<$n>($text, $pattern),)*
+            _ => (),
+        }
+    };
+}
+
+fn check_any(text: &[u8], pattern: &[u8]) {
+    check_len!(
+        text, pattern, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21,
+        22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+    );
+}
+
+#[test]
+fn data_input_from_file() {
+    common::data_input_from_file(
+        INPUT_DATA_PATH,
+        |text, pattern, answer, answer_not_full, answer_not_full_rev| {
+            if let Some(bm) = BMByteN::<5>::from(pattern) {
+                assert_eq!(answer, bm.find_full_all_in(text));
+                assert_eq!(answer_not_full, bm.find_all_in(text));
+                assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+            }
+
+            check_any(text.as_bytes(), pattern.as_bytes());
+        },
+    );
+}
+
+#[test]
+fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        check_any(&text, &pattern);
+    }
+}
+
+#[test]
+fn wrong_len() {
+    assert!(BMByteN::<3>::from("").is_none());
+    assert!(BMByteN::<3>::from("ab").is_none());
+    assert!(BMByteN::<3>::from("abcd").is_none());
+    assert_eq!(b"abc", BMByteN::<3>::from("abc").unwrap().pattern());
+}
+
+#[test]
+fn value_at_only() {
+    let bm = BMByteN::<5>::from("oocoo").unwrap();
+    let text = common::ValueAtOnly(b"coocoocoocoo".to_vec());
+
+    assert_eq!(vec![1, 4, 7], bm.find_full_all_in(&text));
+    assert_eq!(vec![7, 4, 1], bm.rfind_full_all_in(&text));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use std::cell::Cell;
+
+use boyer_moore_magiclen::*;
+
+const ALGORITHMS: [Algorithm; 7] = [
+    Algorithm::MagicLen,
+    Algorithm::Horspool,
+    Algorithm::Sunday,
+    Algorithm::Raita,
+    Algorithm::TunedBoyerMoore,
+    Algorithm::ShiftOr,
+    Algorithm::Bndm,
+];
+
+/// Turn some of the bytes into uppercase.
+fn scramble_case(seed: &Cell<u32>, bytes: &[u8]) -> Vec<u8> {
+    bytes
+        .iter()
+        .map(|c| {
+            seed.set(seed.get().wrapping_mul(1_103_515_245).wrapping_add(12345));
+
+            if (seed.get() >> 16) % 2 == 0 {
+                c.to_ascii_uppercase()
+            } else {
+                *c
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn repetitive() {
+    let seed = Cell::new(0x1357_9BDFu32);
+
+    // the answers are computed from the lowercase cases, and then the cases are scrambled
+    let cases = common::repetitive_cases()
+        .into_iter()
+        .map(|(text, pattern)| (text.to_ascii_lowercase(), pattern.to_ascii_lowercase()))
+        .collect::<Vec<_>>();
+
+    for algorithm in ALGORITHMS {
+        for (good_suffix, bigram, linear) in [
+            (false, false, false),
+            (true, false, false),
+            (false, true, false),
+            (false, false, true),
+        ] {
+            for rev in [true, false] {
+                common::check_against_naive(
+                    cases.iter().cloned(),
+                    &common::Search::ALL,
+                    |pattern| {
+                        BMByte::builder(scramble_case(&seed, pattern))
+                            .algorithm(algorithm)
+                            .good_suffix(good_suffix)
+                            .bigram(bigram)
+                            .linear(linear)
+                            .rev(rev)
+                            .ignore_ascii_case(true)
+                            .build()
+                            .unwrap()
+                    },
+                    |bm, text, kind, limit| {
+                        let text = scramble_case(&seed, text);
+
+                        match (kind, limit) {
+                            (common::Search::Find, 1) => {
+                                bm.find_first_in(&text).into_iter().collect()
+                            },
+                            (common::Search::RFind, 1) => {
+                                bm.rfind_first_in(&text).into_iter().collect()
+                            },
+                            _ => common::search_bmbyte(bm, &text, kind, limit),
+                        }
+                    },
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn case_sensitive_by_default() {
+    let bm = BMByte::from("Host").unwrap();
+
+    assert!(!bm.ignore_ascii_case());
+    assert_eq!(vec![6], bm.find_all_in("host: Host"));
+
+    let bm = BMByte::builder("Host").ignore_ascii_case(true).build().unwrap();
+
+    assert!(bm.ignore_ascii_case());
+    assert_eq!(vec![0, 6], bm.find_all_in("host: Host"));
+    assert_eq!(vec![6, 0], bm.rfind_all_in("host: Host"));
+}
+
+#[test]
+fn non_letters() {
+    // only ASCII letters are folded
+    let bm = BMByte::builder("a[\u{e9}").ignore_ascii_case(true).build().unwrap();
+
+    assert_eq!(vec![0], bm.find_all_in("A[\u{e9}a{\u{e9}A[\u{c9}"));
+}
+
+#[test]
+fn incremental() {
+    let bm = BMByte::builder("SELECT").ignore_ascii_case(true).build().unwrap();
+
+    let mut matcher = IncrementalMatcher::new(&bm);
+
+    assert_eq!(0, matcher.feed(b"; sel").count());
+    assert_eq!(3, matcher.partial_len());
+    assert_eq!(vec![2, 12], matcher.feed(b"Ect 1; SeLeCt 2").collect::<Vec<u64>>());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn records() {
+    let bm = BMByte::builder("\r\nEND\r\n").ignore_ascii_case(true).build().unwrap();
+
+    let records: Vec<Vec<u8>> =
+        "a\r\nend\r\nb\r\nEnd\r\nc".as_bytes().records(&bm).map(|r| r.unwrap()).collect();
+
+    assert_eq!(vec![b"a".to_vec(), b"b".to_vec(), b"c".to_vec()], records);
+}
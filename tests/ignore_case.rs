@@ -0,0 +1,247 @@
+#![cfg(feature = "character")]
+
+mod common;
+
+use boyer_moore_magiclen::*;
+
+const ALPHABET: [char; 14] =
+    ['s', 'S', 'ß', 'ẞ', 'k', 'K', '\u{212a}', 'σ', 'ς', 'Σ', 'f', 'F', 'ﬀ', 'ﬁ'];
+
+fn random_chars(seed: &mut u32, len: usize) -> Vec<char> {
+    (0..len)
+        .map(|_| {
+            *seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+
+            ALPHABET[(*seed >> 16) as usize % ALPHABET.len()]
+        })
+        .collect()
+}
+
+/// The simple case folding of every character of `ALPHABET`.
+const SIMPLE_FOLDED: [char; 14] =
+    ['s', 's', 'ß', 'ß', 'k', 'k', 'k', 'σ', 'σ', 'σ', 'f', 'f', 'ﬀ', 'ﬁ'];
+
+/// The full case folding of every character of `ALPHABET`.
+const FULL_FOLDED: [&str; 14] =
+    ["s", "s", "ss", "ss", "k", "k", "k", "σ", "σ", "σ", "f", "f", "ff", "fi"];
+
+fn alphabet_index(c: char) -> usize {
+    ALPHABET.iter().position(|&a| a == c).unwrap()
+}
+
+fn fold_simple(c: char) -> char {
+    SIMPLE_FOLDED[alphabet_index(c)]
+}
+
+fn fold_full(chars: &[char]) -> Vec<char> {
+    chars.iter().flat_map(|&c| FULL_FOLDED[alphabet_index(c)].chars()).collect()
+}
+
+/// Find the start and the end positions of all matches by the full case folding.
+fn naive_find_full_folding(text: &[char], pattern: &[char]) -> Vec<(usize, usize)> {
+    let pattern = fold_full(pattern);
+
+    let mut result = vec![];
+
+    for start in 0..text.len() {
+        for end in start + 1..=text.len() {
+            let folded = fold_full(&text[start..end]);
+
+            if folded == pattern {
+                result.push((start, end));
+            }
+
+            if folded.len() >= pattern.len() {
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+fn check(bmc: &BMCharacter, text: &Vec<char>, matches: &[(usize, usize)]) {
+    let answer: Vec<usize> = matches.iter().map(|&(start, _)| start).collect();
+    let answer_rev: Vec<usize> = answer.iter().rev().copied().collect();
+
+    let mut answer_not_full = vec![];
+    let mut next_free = 0;
+
+    for &(start, end) in matches.iter() {
+        if start >= next_free {
+            answer_not_full.push(start);
+
+            next_free = end;
+        }
+    }
+
+    let mut answer_not_full_rev = vec![];
+    let mut next_free = usize::MAX;
+
+    for &(start, end) in matches.iter().rev() {
+        if end <= next_free {
+            answer_not_full_rev.push(start);
+
+            next_free = start;
+        }
+    }
+
+    assert_eq!(answer, bmc.find_full_all_in(text));
+    assert_eq!(answer_rev, bmc.rfind_full_all_in(text));
+    assert_eq!(answer_not_full, bmc.find_all_in(text));
+    assert_eq!(answer_not_full_rev, bmc.rfind_all_in(text));
+
+    for limit in 1..3 {
+        assert_eq!(&answer[..answer.len().min(limit)], bmc.find_full_in(text, limit));
+        assert_eq!(&answer_rev[..answer_rev.len().min(limit)], bmc.rfind_full_in(text, limit));
+        assert_eq!(&answer_not_full[..answer_not_full.len().min(limit)], bmc.find_in(text, limit));
+        assert_eq!(
+            &answer_not_full_rev[..answer_not_full_rev.len().min(limit)],
+            bmc.rfind_in(text, limit)
+        );
+    }
+}
+
+/// A character which is compared with the others by the simple case folding.
+#[derive(Debug, Clone, Copy)]
+struct SimpleFolded(char);
+
+impl PartialEq for SimpleFolded {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        fold_simple(self.0) == fold_simple(other.0)
+    }
+}
+
+fn unwrap_folded(chars: &[SimpleFolded]) -> Vec<char> {
+    chars.iter().map(|c| c.0).collect()
+}
+
+#[test]
+fn simple_folding() {
+    let mut seed = 0x2468_ACE0u32;
+
+    let cases: Vec<(Vec<SimpleFolded>, Vec<SimpleFolded>)> = (0..200)
+        .map(|_| {
+            let text = random_chars(&mut seed, 60);
+            let pattern_len = 1 + (seed >> 16) as usize % 4;
+            let pattern = random_chars(&mut seed, pattern_len);
+
+            (
+                text.into_iter().map(SimpleFolded).collect(),
+                pattern.into_iter().map(SimpleFolded).collect(),
+            )
+        })
+        .collect();
+
+    for (good_suffix, rev) in [(false, true), (true, true), (false, false)] {
+        common::check_against_naive(
+            cases.iter().cloned(),
+            &common::Search::ALL,
+            |pattern| {
+                BMCharacter::builder(unwrap_folded(pattern))
+                    .good_suffix(good_suffix)
+                    .rev(rev)
+                    .ignore_case(true)
+                    .build()
+                    .unwrap()
+            },
+            |bmc, text, kind, limit| {
+                common::search_bmcharacter(bmc, &unwrap_folded(text), kind, limit)
+            },
+        );
+    }
+}
+
+#[test]
+fn full_folding() {
+    let mut seed = 0x1357_9BDFu32;
+
+    for _ in 0..200 {
+        let text = random_chars(&mut seed, 60);
+        let pattern_len = 1 + (seed >> 16) as usize % 4;
+        let pattern = random_chars(&mut seed, pattern_len);
+
+        let matches = naive_find_full_folding(&text, &pattern);
+
+        for (good_suffix, rev) in [(false, true), (true, true), (false, false)] {
+            let bmc = BMCharacter::builder(&pattern)
+                .good_suffix(good_suffix)
+                .rev(rev)
+                .full_case_folding(true)
+                .build()
+                .unwrap();
+
+            check(&bmc, &text, &matches);
+        }
+    }
+}
+
+#[test]
+fn case_sensitive_by_default() {
+    let text: Vec<char> = "Σ σ ς".chars().collect();
+
+    let bmc = BMCharacter::from(vec!['σ']).unwrap();
+
+    assert!(!bmc.ignore_case());
+    assert_eq!(vec![2], bmc.find_all_in(&text));
+
+    let bmc = BMCharacter::builder(vec!['σ']).ignore_case(true).build().unwrap();
+
+    assert!(bmc.ignore_case());
+    assert_eq!(vec![0, 2, 4], bmc.find_all_in(&text));
+}
+
+#[test]
+fn positions_in_original_text() {
+    let text: Vec<char> = "ßs MASSE Maße maße".chars().collect();
+
+    // the simple folding does not expand `ß`
+    let bmc = BMCharacter::builder(vec!['s', 's']).ignore_case(true).build().unwrap();
+
+    assert_eq!(vec![5], bmc.find_all_in(&text));
+
+    let bmc = BMCharacter::builder(vec!['m', 'a', 's', 's', 'e'])
+        .full_case_folding(true)
+        .build()
+        .unwrap();
+
+    assert!(bmc.ignore_case());
+    assert_eq!(vec![3, 9, 14], bmc.find_all_in(&text));
+    assert_eq!(vec![14, 9, 3], bmc.rfind_all_in(&text));
+
+    // a match cannot start or end in the middle of `ß`
+    let bmc = BMCharacter::builder(vec!['s', 's']).full_case_folding(true).build().unwrap();
+
+    assert_eq!(vec![0, 5, 11, 16], bmc.find_full_all_in(&text));
+    assert_eq!(vec![0], bmc.find_full_all_in(vec!['ß', 's']));
+}
+
+#[test]
+fn sharp_s() {
+    let text: Vec<char> = "STRASSE Straße STRAẞE strasse".chars().collect();
+
+    for pattern in ["strasse", "STRASSE", "straße", "STRAẞE"] {
+        let bmc = BMCharacter::builder(pattern.chars().collect::<Vec<char>>())
+            .full_case_folding(true)
+            .build()
+            .unwrap();
+
+        assert_eq!(vec![0, 8, 15, 22], bmc.find_all_in(&text), "{pattern}");
+        assert_eq!(vec![22, 15, 8, 0], bmc.rfind_all_in(&text), "{pattern}");
+    }
+
+    // `ẞ` and `ß` are folded into `ss` alike
+    let bmc = BMCharacter::builder(vec!['ẞ']).full_case_folding(true).build().unwrap();
+
+    assert_eq!(vec![0, 1, 3, 4, 6], bmc.find_all_in(vec!['ß', 's', 's', 'ẞ', 'S', 'S', 's', 's']));
+
+    let bmc = BMCharacter::builder(vec!['s', 's']).full_case_folding(true).build().unwrap();
+
+    assert_eq!(vec![0, 1, 2], bmc.find_all_in(vec!['ẞ', 'ß', 'ẞ']));
+
+    // the simple folding keeps `ß`, which is the same character as `ẞ`
+    let bmc = BMCharacter::builder(vec!['ẞ']).ignore_case(true).build().unwrap();
+
+    assert_eq!(vec![0, 3], bmc.find_all_in(vec!['ß', 's', 's', 'ẞ']));
+}
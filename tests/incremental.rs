@@ -0,0 +1,90 @@
+mod common;
+
+use boyer_moore_magiclen::*;
+
+fn search(bm: &BMByte, text: &[u8], full: bool, piece_len: usize) -> Vec<usize> {
+    let mut matcher = IncrementalMatcher::new(bm).full(full);
+
+    let mut result = Vec::new();
+
+    for piece in text.chunks(piece_len) {
+        result.extend(matcher.feed(piece).map(|i| i as usize));
+    }
+
+    assert_eq!(text.len() as u64, matcher.finish());
+
+    result
+}
+
+#[test]
+fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        let bm = BMByte::from(&pattern).unwrap();
+
+        let answer = common::naive_find_full(&text, &pattern);
+        let answer_not_full = common::naive_find(&text, &pattern);
+
+        for piece_len in common::PIECE_LENS {
+            assert_eq!(answer, search(&bm, &text, true, piece_len));
+            assert_eq!(answer_not_full, search(&bm, &text, false, piece_len));
+        }
+    }
+}
+
+#[test]
+fn straddling() {
+    let bm = BMByte::from("\r\n\r\n").unwrap();
+
+    let text = b"GET / HTTP/1.1\r\nHost: a\r\n\r\nbody\r\n\r\n\r\n";
+
+    for piece_len in 1..text.len() + 2 {
+        assert_eq!(vec![23, 31, 33], search(&bm, text, true, piece_len));
+        assert_eq!(vec![23, 31], search(&bm, text, false, piece_len));
+    }
+}
+
+#[test]
+fn partial_len() {
+    let bm = BMByte::from("abcab").unwrap();
+
+    let mut matcher = IncrementalMatcher::new(&bm);
+
+    assert_eq!(None, matcher.feed(b"xxab").next());
+    assert_eq!(2, matcher.partial_len());
+    assert_eq!(None, matcher.feed(b"").next());
+    assert_eq!(2, matcher.partial_len());
+    assert_eq!(Some(2), matcher.feed(b"cabc").next());
+    assert_eq!(3, matcher.partial_len());
+    assert_eq!(Some(8), matcher.feed(b"abcab").next());
+    assert_eq!(2, matcher.partial_len());
+    assert_eq!(13, matcher.position());
+
+    assert_eq!(13, matcher.finish());
+    assert_eq!(0, matcher.partial_len());
+    assert_eq!(vec![0], matcher.feed(b"abcab").collect::<Vec<u64>>());
+}
+
+#[test]
+fn partial_len_pieces() {
+    for (text, pattern) in common::repetitive_cases() {
+        let bm = BMByte::from(&pattern).unwrap();
+
+        for piece_len in 1..2 * pattern.len() + 2 {
+            let mut matcher = IncrementalMatcher::new(&bm);
+            let mut consumed = 0;
+
+            for piece in text.chunks(piece_len) {
+                matcher.feed(piece).for_each(drop);
+
+                consumed += piece.len();
+
+                let partial_len = (0..pattern.len())
+                    .rev()
+                    .find(|&k| k <= consumed && text[consumed - k..consumed] == pattern[..k])
+                    .unwrap();
+
+                assert_eq!(partial_len, matcher.partial_len());
+            }
+        }
+    }
+}
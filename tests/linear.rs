@@ -0,0 +1,140 @@
+mod common;
+
+use boyer_moore_magiclen::*;
+
+const INPUT_DATA_PATH: &str = r"tests/data/utf8.txt";
+
+#[test]
+fn data_input_from_file() {
+    common::data_input_from_file(
+        INPUT_DATA_PATH,
+        |text, pattern, answer, answer_not_full, answer_not_full_rev| {
+            for (rev, good_suffix) in [(true, false), (true, true), (false, false), (false, true)] {
+                let bm = BMByte::builder(pattern)
+                    .rev(rev)
+                    .good_suffix(good_suffix)
+                    .algorithm(Algorithm::MagicLen)
+                    .linear(true)
+                    .build()
+                    .unwrap();
+
+                assert_eq!(answer, bm.find_full_all_in(text));
+                assert_eq!(
+                    answer.iter().rev().copied().collect::<Vec<usize>>(),
+                    bm.rfind_full_all_in(text)
+                );
+                assert_eq!(answer_not_full, bm.find_all_in(text));
+                assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+            }
+        },
+    );
+}
+
+fn adversarial_cases() -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut cases = common::repetitive_cases();
+
+    for m in 1..12 {
+        for n in [m, m + 1, 2 * m + 3, 64, 200] {
+            let a = vec![b'a'; n];
+
+            let mut ab = a.clone();
+            ab[n - 1] = b'b';
+
+            let mut ba = a.clone();
+            ba[0] = b'b';
+
+            let mut b_every_m = a.clone();
+            b_every_m.iter_mut().step_by(m + 1).for_each(|c| *c = b'b');
+
+            let mut pattern_b_head = vec![b'a'; m];
+            pattern_b_head[0] = b'b';
+
+            let mut pattern_b_tail = vec![b'a'; m];
+            pattern_b_tail[m - 1] = b'b';
+
+            for text in [&a, &ab, &ba, &b_every_m] {
+                for pattern in [&vec![b'a'; m], &pattern_b_head, &pattern_b_tail] {
+                    cases.push((text.clone(), pattern.clone()));
+                }
+            }
+        }
+    }
+
+    cases
+}
+
+#[test]
+fn adversarial() {
+    for good_suffix in [false, true] {
+        common::check_against_naive(
+            adversarial_cases(),
+            &common::Search::ALL,
+            |pattern| {
+                BMByte::builder(pattern)
+                    .good_suffix(good_suffix)
+                    .algorithm(Algorithm::MagicLen)
+                    .linear(true)
+                    .build()
+                    .unwrap()
+            },
+            common::search_bmbyte,
+        );
+    }
+}
+
+#[test]
+fn adversarial_bndm() {
+    common::check_against_naive(
+        adversarial_cases(),
+        &common::Search::ALL,
+        |pattern| {
+            let bm =
+                BMByte::builder(pattern).algorithm(Algorithm::Bndm).linear(true).build().unwrap();
+
+            // BNDM is replaced by Shift-Or, which is linear
+            assert_eq!(Algorithm::ShiftOr, bm.algorithm());
+
+            bm
+        },
+        common::search_bmbyte,
+    );
+
+    let bm =
+        BMByte::builder(vec![b'a'; 65]).algorithm(Algorithm::Bndm).linear(true).build().unwrap();
+
+    assert_eq!(Algorithm::MagicLen, bm.algorithm());
+
+    let bm = BMByte::builder("baaaa").algorithm(Algorithm::Bndm).build().unwrap();
+
+    assert_eq!(Algorithm::Bndm, bm.algorithm());
+}
+
+#[test]
+fn adversarial_large() {
+    const N: usize = 1_000_000;
+    const M: usize = 1_000;
+
+    let text = vec![b'a'; N];
+
+    let mut pattern = vec![b'a'; M];
+    pattern[0] = b'b';
+
+    let bm = BMByte::builder(&pattern).algorithm(Algorithm::MagicLen).linear(true).build().unwrap();
+
+    assert!(bm.find_all_in(&text).is_empty());
+    assert!(bm.find_full_all_in(&text).is_empty());
+
+    let mut pattern = vec![b'a'; M];
+    pattern[M - 1] = b'b';
+
+    let bm = BMByte::builder(&pattern).algorithm(Algorithm::MagicLen).linear(true).build().unwrap();
+
+    assert!(bm.rfind_all_in(&text).is_empty());
+    assert!(bm.rfind_full_all_in(&text).is_empty());
+
+    let bm =
+        BMByte::builder(vec![b'a'; M]).algorithm(Algorithm::MagicLen).linear(true).build().unwrap();
+
+    assert_eq!(N - M + 1, bm.find_full_all_in(&text).len());
+    assert_eq!(N - M + 1, bm.rfind_full_all_in(&text).len());
+}
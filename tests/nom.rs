@@ -0,0 +1,89 @@
+#![cfg(feature = "nom")]
+
+mod common;
+
+use boyer_moore_magiclen::{
+    byte::nom::{complete, streaming},
+    BMByte,
+};
+use nom::{
+    error::{Error, ErrorKind},
+    Err, IResult, Needed,
+};
+
+#[test]
+fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        let bm = BMByte::from(&pattern).unwrap();
+
+        let text = text.as_slice();
+
+        let first = common::naive_find(text, &pattern).first().copied();
+        let last = common::naive_rfind(text, &pattern).first().copied();
+
+        let split = |index: usize| (&text[index..], &text[..index]);
+
+        let result: IResult<&[u8], &[u8]> = complete::take_until_bm(&bm)(text);
+        assert_eq!(
+            first.map(split).ok_or(Err::Error(nom::error::Error::new(text, ErrorKind::TakeUntil))),
+            result
+        );
+
+        let result: IResult<&[u8], &[u8]> = complete::take_until_bm_rev(&bm)(text);
+        assert_eq!(
+            last.map(split).ok_or(Err::Error(nom::error::Error::new(text, ErrorKind::TakeUntil))),
+            result
+        );
+
+        let result: IResult<&[u8], &[u8]> = streaming::take_until_bm(&bm)(text);
+
+        match first {
+            Some(index) => assert_eq!(Ok(split(index)), result),
+            None => assert!(matches!(result, Err(Err::Incomplete(Needed::Size(_))))),
+        }
+
+        let result: IResult<&[u8], &[u8]> = streaming::take_until_bm_rev(&bm)(text);
+
+        match last {
+            Some(index) => assert_eq!(Ok(split(index)), result),
+            None => assert!(matches!(result, Err(Err::Incomplete(Needed::Size(_))))),
+        }
+    }
+}
+
+#[test]
+fn needed() {
+    let bm = BMByte::from("abcab").unwrap();
+
+    for (text, needed) in [("", 5), ("x", 5), ("xa", 4), ("xab", 3), ("abca", 1), ("xabcxab", 3)] {
+        let result: IResult<&str, &str> = streaming::take_until_bm(&bm)(text);
+
+        assert_eq!(Err(Err::Incomplete(Needed::new(needed))), result, "{text}");
+    }
+}
+
+#[test]
+fn str() {
+    let bm = BMByte::from("的").unwrap();
+
+    let result: IResult<&str, &str> = complete::take_until_bm(&bm)("我的貓的名字");
+    assert_eq!(Ok(("的貓的名字", "我")), result);
+
+    let result: IResult<&str, &str> = complete::take_until_bm_rev(&bm)("我的貓的名字");
+    assert_eq!(Ok(("的名字", "我的貓")), result);
+
+    // `©` is encoded as `C2 A9`, so `A9` occurs in the middle of it
+    let bm = BMByte::from([0xA9].as_slice()).unwrap();
+
+    let result: IResult<&str, &str> = complete::take_until_bm(&bm)("café ©");
+    assert_eq!(Err(Err::Error(Error::new("café ©", ErrorKind::TakeUntil))), result);
+
+    let result: IResult<&str, &str> = complete::take_until_bm_rev(&bm)("©");
+    assert_eq!(Err(Err::Error(Error::new("©", ErrorKind::TakeUntil))), result);
+
+    let result: IResult<&str, &str> = streaming::take_until_bm(&bm)("©");
+    assert_eq!(Err(Err::Error(Error::new("©", ErrorKind::TakeUntil))), result);
+
+    let result: IResult<&[u8], &[u8]> = streaming::take_until_bm_rev(&bm)("©".as_bytes());
+    assert_eq!(Ok((&[0xA9][..], &[0xC2][..])), result);
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "rayon")]
+
+mod common;
+
+use boyer_moore_magiclen::*;
+
+fn random_text(len: usize, alphabet: u32) -> Vec<u8> {
+    let mut seed = 0x1234_5678u32;
+
+    (0..len)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+
+            b'a' + ((seed >> 16) % alphabet) as u8
+        })
+        .collect()
+}
+
+fn check(text: &Vec<u8>, pattern: &[u8]) {
+    for good_suffix in [false, true] {
+        let bm = BMByte::builder(pattern.to_vec()).good_suffix(good_suffix).build().unwrap();
+
+        let answer = bm.find_all_in(text);
+
+        assert_eq!(bm.find_full_all_in(text), bm.par_find_full_all_in(text));
+        assert_eq!(answer, bm.par_find_all_in(text));
+        assert_eq!(answer.len(), bm.par_count_in(text));
+    }
+}
+
+#[test]
+fn random() {
+    let text = random_text(1 << 21, 3);
+
+    for pattern in [&b"a"[..], b"ab", b"abcab", b"abcabcabcabcabcabcabc", &text[777..1777]] {
+        check(&text, pattern);
+    }
+}
+
+#[test]
+fn repetitive() {
+    // every match overlaps the next one, so the boundaries of the chunks always fall inside a match
+    let text = vec![b'a'; 1 << 21];
+
+    for pattern_len in [1, 2, 3, 7, 100, 65537] {
+        check(&text, &text[..pattern_len]);
+    }
+
+    let text = b"abc".repeat(1 << 20);
+
+    for pattern in [&b"abca"[..], b"cabcabcab", b"bcabcabcabcabcabcabcabcabcabcabcabcabcabca"] {
+        check(&text, pattern);
+    }
+}
+
+#[test]
+fn small() {
+    let bm = BMByte::from("oocoo").unwrap();
+
+    assert!(bm.par_find_full_all_in("").is_empty());
+    assert_eq!(vec![1, 4, 7], bm.par_find_full_all_in("coocoocoocoo"));
+    assert_eq!(vec![1, 7], bm.par_find_all_in("coocoocoocoo"));
+    assert_eq!(2, bm.par_count_in("coocoocoocoo"));
+}
+
+#[test]
+fn value_at_only() {
+    let text = random_text(1 << 21, 3);
+
+    let bm = BMByte::from("abcab").unwrap();
+
+    let answer_full = bm.find_full_all_in(&text);
+    let answer = bm.find_all_in(&text);
+
+    let text = common::ValueAtOnly(text);
+
+    assert_eq!(answer_full, bm.par_find_full_all_in(&text));
+    assert_eq!(answer, bm.par_find_all_in(&text));
+}
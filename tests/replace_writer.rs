@@ -0,0 +1,84 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::io::Write;
+
+use boyer_moore_magiclen::*;
+
+/// Replace the non-overlapping occurrences like `str::replace`.
+fn naive_replace(text: &[u8], pattern: &[u8], replacement: &[u8]) -> Vec<u8> {
+    let mut result = Vec::new();
+    let mut start = 0;
+
+    for i in common::naive_find(text, pattern) {
+        result.extend_from_slice(&text[start..i]);
+        result.extend_from_slice(replacement);
+        start = i + pattern.len();
+    }
+
+    result.extend_from_slice(&text[start..]);
+
+    result
+}
+
+#[test]
+fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        let bm = BMByte::from(&pattern).unwrap();
+
+        for replacement in [&b""[..], b"_", b"<replaced>"] {
+            let answer = naive_replace(&text, &pattern, replacement);
+
+            for piece_len in common::PIECE_LENS {
+                let mut writer = ReplaceWriter::new(&bm, replacement, Vec::new());
+
+                for piece in text.chunks(piece_len) {
+                    writer.write_all(piece).unwrap();
+                }
+
+                assert_eq!(answer, writer.into_inner().unwrap());
+            }
+        }
+    }
+}
+
+#[test]
+fn hold_back() {
+    let bm = BMByte::from("secret").unwrap();
+
+    let mut writer = ReplaceWriter::new(&bm, "******", Vec::new());
+
+    writer.write_all(b"password: sec").unwrap();
+
+    // only the partial match is held back
+    assert_eq!(b"password: ", writer.get_ref().as_slice());
+
+    writer.write_all(b"re").unwrap();
+    writer.write_all(b"t!").unwrap();
+
+    assert_eq!(b"password: ******!", writer.get_ref().as_slice());
+
+    writer.write_all(b" sec").unwrap();
+
+    // an occurrence straddling a `flush` call is not replaced
+    writer.flush().unwrap();
+    writer.write_all(b"ret").unwrap();
+
+    assert_eq!(b"password: ******! secret", writer.into_inner().unwrap().as_slice());
+}
+
+#[test]
+fn drop() {
+    let bm = BMByte::from("secret").unwrap();
+
+    let mut output = Vec::new();
+
+    {
+        let mut writer = ReplaceWriter::new(&bm, "******", &mut output);
+
+        writer.write_all(b"secret sec").unwrap();
+    }
+
+    assert_eq!(b"****** sec", output.as_slice());
+}
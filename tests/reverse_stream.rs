@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+use boyer_moore_magiclen::*;
+
+fn search<R: Read + Seek>(bm: &BMByte, reader: R, full: bool, buffer_size: usize) -> Vec<usize> {
+    ReverseStreamSearcher::new(bm, reader)
+        .full(full)
+        .buffer_size(buffer_size)
+        .map(|r| r.unwrap() as usize)
+        .collect()
+}
+
+#[test]
+fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        let bm = BMByte::from(&pattern).unwrap();
+
+        let answer =
+            common::naive_find_full(&text, &pattern).into_iter().rev().collect::<Vec<usize>>();
+        let answer_not_full = common::naive_rfind(&text, &pattern);
+
+        for buffer_size in common::PIECE_LENS {
+            assert_eq!(answer, search(&bm, Cursor::new(&text), true, buffer_size));
+            assert_eq!(answer_not_full, search(&bm, Cursor::new(&text), false, buffer_size));
+        }
+    }
+}
+
+#[test]
+fn straddling() {
+    let bm = BMByte::from("\r\n\r\n").unwrap();
+
+    let text = b"GET / HTTP/1.1\r\nHost: a\r\n\r\nbody\r\n\r\n\r\n";
+
+    for buffer_size in 1..text.len() + 2 {
+        assert_eq!(vec![33, 31, 23], search(&bm, Cursor::new(text), true, buffer_size));
+        assert_eq!(vec![33, 23], search(&bm, Cursor::new(text), false, buffer_size));
+    }
+}
+
+#[test]
+fn error() {
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::Other, "broken"))
+        }
+    }
+
+    impl Seek for FailingReader {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            match pos {
+                SeekFrom::End(_) => Ok(100),
+                _ => Ok(0),
+            }
+        }
+    }
+
+    let bm = BMByte::from("oocoo").unwrap();
+
+    let mut searcher = ReverseStreamSearcher::new(&bm, FailingReader);
+
+    assert!(searcher.next().unwrap().is_err());
+    assert!(searcher.next().is_none());
+}
+
+#[test]
+fn empty() {
+    let bm = BMByte::from("oocoo").unwrap();
+
+    assert!(ReverseStreamSearcher::new(&bm, Cursor::new(b"")).next().is_none());
+}
@@ -0,0 +1,248 @@
+mod common;
+
+use boyer_moore_magiclen::*;
+
+fn random_bytes(seed: &mut u32, len: usize, alphabet: u32) -> Vec<u8> {
+    (0..len)
+        .map(|_| {
+            *seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+
+            b'a' + ((*seed >> 16) % alphabet) as u8
+        })
+        .collect()
+}
+
+fn naive_find(text: &[u8], patterns: &[Vec<u8>], kind: MatchKind) -> Vec<(usize, usize)> {
+    let mut result = vec![];
+
+    let mut start = 0;
+
+    while start < text.len() {
+        let matched: Vec<usize> =
+            (0..patterns.len()).filter(|&id| text[start..].starts_with(&patterns[id])).collect();
+
+        let chosen = match kind {
+            MatchKind::All => {
+                result.extend(matched.into_iter().map(|id| (id, start)));
+
+                start += 1;
+
+                continue;
+            },
+            MatchKind::LeftmostFirst => matched.first().copied(),
+            MatchKind::LeftmostLongest => {
+                matched.iter().copied().rev().max_by_key(|&id| patterns[id].len())
+            },
+        };
+
+        match chosen {
+            Some(id) => {
+                result.push((id, start));
+
+                start += patterns[id].len();
+            },
+            None => start += 1,
+        }
+    }
+
+    result
+}
+
+fn naive_rfind(text: &[u8], patterns: &[Vec<u8>], kind: MatchKind) -> Vec<(usize, usize)> {
+    let text: Vec<u8> = text.iter().rev().copied().collect();
+    let patterns: Vec<Vec<u8>> =
+        patterns.iter().map(|p| p.iter().rev().copied().collect()).collect();
+
+    naive_find(&text, &patterns, kind)
+        .into_iter()
+        .map(|(id, i)| (id, text.len() - i - patterns[id].len()))
+        .collect()
+}
+
+fn check(text: &[u8], patterns: &[Vec<u8>]) {
+    for kind in [MatchKind::LeftmostFirst, MatchKind::LeftmostLongest, MatchKind::All] {
+        let set = BMByteSet::builder(patterns.iter().map(|p| p.as_slice()))
+            .match_kind(kind)
+            .build()
+            .unwrap();
+
+        let answer = naive_find(text, patterns, kind);
+        let answer_rev = naive_rfind(text, patterns, kind);
+
+        assert_eq!(answer, set.find_all_in(text));
+        assert_eq!(answer_rev, set.rfind_all_in(text));
+
+        assert_eq!(answer.first().copied(), set.find_first_in(text));
+        assert_eq!(answer_rev.first().copied(), set.rfind_first_in(text));
+
+        for limit in [1, 2, 5] {
+            assert_eq!(&answer[..limit.min(answer.len())], set.find_in(text, limit));
+            assert_eq!(&answer_rev[..limit.min(answer_rev.len())], set.rfind_in(text, limit));
+        }
+    }
+}
+
+#[test]
+fn repetitive() {
+    // a set of a single pattern is searched like `BMByte`
+    common::check_against_naive(
+        common::repetitive_cases(),
+        &common::Search::ALL,
+        |pattern| {
+            let all = BMByteSet::builder([pattern]).match_kind(MatchKind::All).build().unwrap();
+            let first = BMByteSet::from([pattern]).unwrap();
+
+            (all, first)
+        },
+        |(all, first), text, kind, limit| {
+            let result = match (kind, limit) {
+                (common::Search::FindFull, 0) => all.find_all_in(text),
+                (common::Search::FindFull, _) => all.find_in(text, limit),
+                (common::Search::RFindFull, 0) => all.rfind_all_in(text),
+                (common::Search::RFindFull, _) => all.rfind_in(text, limit),
+                (common::Search::Find, 0) => first.find_all_in(text),
+                (common::Search::Find, _) => first.find_in(text, limit),
+                (common::Search::RFind, 0) => first.rfind_all_in(text),
+                (common::Search::RFind, _) => first.rfind_in(text, limit),
+            };
+
+            result.into_iter().map(|(_, i)| i).collect()
+        },
+    );
+
+    for (text, pattern) in common::repetitive_cases() {
+        if pattern.len() > 1 {
+            // the pattern with its last byte or its first byte removed
+            let patterns =
+                vec![pattern.clone(), pattern[..pattern.len() - 1].to_vec(), pattern[1..].to_vec()];
+
+            check(&text, &patterns);
+        }
+    }
+}
+
+#[test]
+fn random() {
+    let mut seed = 0x1234_5678u32;
+
+    for alphabet in [2, 3, 4, 26] {
+        for _ in 0..50 {
+            let text = random_bytes(&mut seed, 500, alphabet);
+
+            let patterns: Vec<Vec<u8>> = (1..=5)
+                .map(|i| {
+                    let len = 1 + (seed >> 16) as usize % (i * 2);
+
+                    random_bytes(&mut seed, len, alphabet)
+                })
+                .collect();
+
+            check(&text, &patterns);
+        }
+    }
+}
+
+#[test]
+fn match_kind() {
+    let patterns = ["abc", "abcdef", "bcd", "cdefg"];
+
+    let first = BMByteSet::from(patterns).unwrap();
+
+    assert_eq!(MatchKind::LeftmostFirst, first.match_kind());
+    assert_eq!(vec![(0, 0)], first.find_all_in("abcdefg"));
+
+    let longest =
+        BMByteSet::builder(patterns).match_kind(MatchKind::LeftmostLongest).build().unwrap();
+
+    assert_eq!(vec![(1, 0)], longest.find_all_in("abcdefg"));
+
+    let all = BMByteSet::builder(patterns).match_kind(MatchKind::All).build().unwrap();
+
+    assert_eq!(vec![(0, 0), (1, 0), (2, 1), (3, 2)], all.find_all_in("abcdefg"));
+    assert_eq!(vec![(3, 2), (1, 0), (2, 1), (0, 0)], all.rfind_all_in("abcdefg"));
+}
+
+#[test]
+fn empty() {
+    assert!(BMByteSet::from(Vec::<&str>::new()).is_none());
+    assert!(BMByteSet::from(["a", ""]).is_none());
+
+    let set = BMByteSet::from(["ab", "b"]).unwrap();
+
+    assert_eq!(2, set.len());
+    assert_eq!(b"b", set.pattern(1));
+    assert!(set.find_all_in("").is_empty());
+    assert!(set.rfind_all_in("").is_empty());
+}
+
+fn naive_replace_all(text: &[u8], pairs: &[(Vec<u8>, Vec<u8>)]) -> Vec<u8> {
+    let patterns: Vec<Vec<u8>> = pairs.iter().map(|(needle, _)| needle.clone()).collect();
+
+    let mut result = Vec::new();
+    let mut handled = 0;
+
+    for (id, position) in naive_find(text, &patterns, MatchKind::LeftmostLongest) {
+        result.extend_from_slice(&text[handled..position]);
+        result.extend_from_slice(&pairs[id].1);
+
+        handled = position + patterns[id].len();
+    }
+
+    result.extend_from_slice(&text[handled..]);
+
+    result
+}
+
+#[test]
+fn replace_all() {
+    let mut seed = 0x8765_4321u32;
+
+    for alphabet in [2, 3, 26] {
+        for _ in 0..50 {
+            let text = random_bytes(&mut seed, 300, alphabet);
+
+            let pairs: Vec<(Vec<u8>, Vec<u8>)> = (1..=4)
+                .map(|i| {
+                    let len = 1 + (seed >> 16) as usize % (i * 2);
+
+                    let needle = random_bytes(&mut seed, len, alphabet);
+
+                    (needle, format!("<{i}>").into_bytes())
+                })
+                .collect();
+
+            assert_eq!(naive_replace_all(&text, &pairs), BMByteSet::replace_all(&text, &pairs));
+        }
+    }
+}
+
+#[test]
+fn replace_all_simultaneously() {
+    // the replacements are never matched again
+    assert_eq!(
+        b"{b}{a}b{b}".as_slice(),
+        BMByteSet::replace_all("abba", &[("a", "{b}"), ("b", "{a}"), ("bb", "{a}b")])
+    );
+
+    // the empty needles are ignored
+    assert_eq!(b"x-y".as_slice(), BMByteSet::replace_all("x-y", &[("", "z")]));
+    assert_eq!(b"x-y".as_slice(), BMByteSet::replace_all("x-y", &Vec::<(&str, &str)>::new()));
+
+    let set = BMByteSet::builder(["aa", "a"]).match_kind(MatchKind::All).build().unwrap();
+
+    assert_eq!(b"<><>+".as_slice(), set.replace_all_with("aaaaa", &["<>", "+"]));
+}
+
+#[test]
+fn value_at_only() {
+    let set = BMByteSet::from(["cat", "dog"]).unwrap();
+    let text = common::ValueAtOnly(b"cat dog".to_vec());
+
+    assert_eq!(vec![(0, 0), (1, 4)], set.find_all_in(&text));
+    assert_eq!(vec![(1, 4), (0, 0)], set.rfind_all_in(&text));
+    assert_eq!(b"dog cat".as_slice(), set.replace_all_with(&text, &["dog", "cat"]));
+    assert_eq!(
+        b"dog cat".as_slice(),
+        BMByteSet::replace_all(&text, &[("cat", "dog"), ("dog", "cat")])
+    );
+}
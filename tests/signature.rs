@@ -0,0 +1,186 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::io::{self, Read};
+
+use boyer_moore_magiclen::byte::{FileFormat, Signature, SignatureHit, SignatureScanner};
+
+/// A reader which returns at most a few bytes on each call.
+struct Trickle<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl<'a> Read for Trickle<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.step = self.step % 7 + 1;
+
+        let len = self.step.min(buf.len()).min(self.data.len());
+
+        buf[..len].copy_from_slice(&self.data[..len]);
+
+        self.data = &self.data[len..];
+
+        Ok(len)
+    }
+}
+
+fn hit(kind: FileFormat, start: u64, end: Option<u64>) -> SignatureHit<FileFormat> {
+    SignatureHit {
+        kind,
+        start,
+        end,
+    }
+}
+
+#[test]
+fn builtin() {
+    let png = b"\x89PNG\r\n\x1a\n....IEND\xae\x42\x60\x82";
+    let zip = b"PK\x03\x04....PK\x05\x06..................";
+    let jpeg = b"\xff\xd8\xff\xe0....\xff\xd9";
+
+    let mut blob = vec![0; 100];
+
+    blob.extend_from_slice(png);
+    blob.extend_from_slice(&[0; 10]);
+    blob.extend_from_slice(zip);
+    blob.extend_from_slice(b"\x1f\x8b\x08");
+    blob.extend_from_slice(jpeg);
+    blob.extend_from_slice(b"%PDF-1.4");
+
+    let png_start = 100;
+    let zip_start = png_start + png.len() as u64 + 10;
+    let gzip_start = zip_start + zip.len() as u64;
+    let jpeg_start = gzip_start + 3;
+    let pdf_start = jpeg_start + jpeg.len() as u64;
+
+    let answer = vec![
+        hit(FileFormat::Png, png_start, Some(png_start + png.len() as u64)),
+        hit(FileFormat::Zip, zip_start, Some(zip_start + zip.len() as u64)),
+        hit(FileFormat::Gzip, gzip_start, None),
+        hit(FileFormat::Jpeg, jpeg_start, Some(jpeg_start + jpeg.len() as u64)),
+        hit(FileFormat::Pdf, pdf_start, None),
+    ];
+
+    let scanner = SignatureScanner::builtin();
+
+    assert_eq!(answer, scanner.scan(blob.as_slice()));
+    assert_eq!(answer, scanner.scan_reader(blob.as_slice()).unwrap());
+    assert_eq!(answer, scanner.scan(common::ValueAtOnly(blob.clone())));
+    assert_eq!(
+        answer,
+        scanner
+            .scan_reader(Trickle {
+                data: &blob, step: 0
+            })
+            .unwrap()
+    );
+}
+
+#[test]
+fn straddling() {
+    let scanner = SignatureScanner::builtin();
+
+    let mut blob = Vec::new();
+    let mut answer = Vec::new();
+
+    // put the files across the boundaries of the buffers
+    for i in 0..300 {
+        blob.resize(i * 997, b'.');
+
+        let start = blob.len() as u64;
+
+        blob.extend_from_slice(b"%PDF-1.7 %%EOF");
+
+        answer.push(hit(FileFormat::Pdf, start, Some(start + 14)));
+    }
+
+    assert_eq!(answer, scanner.scan(blob.as_slice()));
+    assert_eq!(answer, scanner.scan_reader(blob.as_slice()).unwrap());
+}
+
+#[test]
+fn custom() {
+    let scanner = SignatureScanner::new(vec![
+        Signature::new("tar", "ustar").unwrap().offset(257),
+        Signature::new("gif", "GIF89a").unwrap().trailer("\x00\x3b", 0).max_len(16),
+    ])
+    .unwrap();
+
+    assert_eq!(2, scanner.signatures().len());
+
+    let mut blob = b"ustar \x00\x3bGIF89a....\x00\x3b GIF89a.................\x00\x3b".to_vec();
+
+    blob.extend_from_slice(&[0; 300]);
+
+    let tar_start = blob.len() as u64;
+
+    blob.extend_from_slice(&[0; 257]);
+    blob.extend_from_slice(b"ustar");
+
+    let hits = scanner.scan(blob.as_slice());
+
+    // the first `ustar` is too close to the start, and the trailer before a magic is ignored
+    assert_eq!(
+        vec![
+            SignatureHit {
+                kind: "gif", start: 8, end: Some(20)
+            },
+            SignatureHit {
+                kind: "gif", start: 21, end: None
+            },
+            SignatureHit {
+                kind: "tar", start: tar_start, end: None
+            },
+        ],
+        hits
+    );
+
+    assert_eq!(hits, scanner.scan_reader(blob.as_slice()).unwrap());
+}
+
+#[test]
+fn empty() {
+    assert!(Signature::new(0, "").is_none());
+    assert!(SignatureScanner::<u8>::new(vec![]).is_none());
+
+    let scanner = SignatureScanner::builtin();
+
+    assert!(scanner.scan("").is_empty());
+    assert!(scanner.scan_reader(io::empty()).unwrap().is_empty());
+}
+
+#[test]
+fn contained() {
+    let scanner = SignatureScanner::builtin();
+
+    // the local file headers of the other entries are inside the first hit
+    let zip = b"PK\x03\x04a..PK\x03\x04b..PK\x03\x04c..PK\x01\x02...PK\x05\x06..................";
+
+    let mut blob = zip.to_vec();
+    blob.extend_from_slice(b"PK\x03\x04d..");
+
+    assert_eq!(
+        vec![
+            hit(FileFormat::Zip, 0, Some(zip.len() as u64)),
+            hit(FileFormat::Zip, zip.len() as u64, None)
+        ],
+        scanner.scan(blob.as_slice())
+    );
+
+    // the thumbnail does not end the JPEG file
+    let jpeg = b"\xff\xd8\xff\xe1exif\xff\xd8\xff\xdbthumbnail\xff\xd9image\xff\xd9";
+
+    let mut blob = b"\xff\xd8\xff".to_vec();
+    blob.extend_from_slice(jpeg);
+
+    assert_eq!(
+        vec![hit(FileFormat::Jpeg, 0, None), hit(FileFormat::Jpeg, 3, Some(blob.len() as u64))],
+        scanner.scan(blob.as_slice())
+    );
+    assert_eq!(
+        vec![hit(FileFormat::Jpeg, 0, Some(jpeg.len() as u64))],
+        scanner.scan(jpeg.as_slice())
+    );
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "std")]
+
+mod common;
+
+use std::io::{self, Read};
+
+use boyer_moore_magiclen::*;
+
+/// A reader which returns at most `step` bytes on each call and is interrupted before every read.
+struct ShortReader<'a> {
+    data:        &'a [u8],
+    step:        usize,
+    interrupted: bool,
+}
+
+impl Read for ShortReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.interrupted = !self.interrupted;
+
+        if self.interrupted {
+            return Err(io::ErrorKind::Interrupted.into());
+        }
+
+        let len = self.step.min(buf.len()).min(self.data.len());
+
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+
+        Ok(len)
+    }
+}
+
+fn search<R: Read>(bm: &BMByte, reader: R, full: bool, buffer_size: usize) -> Vec<usize> {
+    StreamSearcher::new(bm, reader)
+        .full(full)
+        .buffer_size(buffer_size)
+        .map(|r| r.unwrap() as usize)
+        .collect()
+}
+
+#[test]
+fn repetitive() {
+    for (text, pattern) in common::repetitive_cases() {
+        let bm = BMByte::from(&pattern).unwrap();
+
+        let answer = common::naive_find_full(&text, &pattern);
+        let answer_not_full = common::naive_find(&text, &pattern);
+
+        for buffer_size in common::PIECE_LENS {
+            assert_eq!(answer, search(&bm, text.as_slice(), true, buffer_size));
+            assert_eq!(answer_not_full, search(&bm, text.as_slice(), false, buffer_size));
+
+            for step in [1, 2, 7] {
+                let reader = ShortReader {
+                    data: &text,
+                    step,
+                    interrupted: false,
+                };
+
+                assert_eq!(answer_not_full, search(&bm, reader, false, buffer_size));
+            }
+        }
+    }
+}
+
+#[test]
+fn straddling() {
+    let bm = BMByte::from("\r\n\r\n").unwrap();
+
+    let text = b"GET / HTTP/1.1\r\nHost: a\r\n\r\nbody\r\n\r\n\r\n";
+
+    for buffer_size in 1..text.len() + 2 {
+        assert_eq!(vec![23, 31, 33], search(&bm, &text[..], true, buffer_size));
+        assert_eq!(vec![23, 31], search(&bm, &text[..], false, buffer_size));
+    }
+}
+
+#[test]
+fn error() {
+    struct FailingReader(bool);
+
+    impl Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0 {
+                Err(io::Error::new(io::ErrorKind::Other, "broken"))
+            } else {
+                self.0 = true;
+
+                buf[..5].copy_from_slice(b"oocoo");
+
+                Ok(5)
+            }
+        }
+    }
+
+    let bm = BMByte::from("oocoo").unwrap();
+
+    let mut searcher = StreamSearcher::new(&bm, FailingReader(false)).buffer_size(5);
+
+    assert_eq!(0, searcher.next().unwrap().unwrap());
+    assert!(searcher.next().unwrap().is_err());
+    assert!(searcher.next().is_none());
+}
+
+#[test]
+fn empty() {
+    let bm = BMByte::from("oocoo").unwrap();
+
+    let mut searcher = StreamSearcher::new(&bm, io::empty());
+
+    assert!(searcher.next().is_none());
+    assert_eq!(0, searcher.bytes_read());
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use std::fs;
+
+use boyer_moore_magiclen::*;
+
+const INPUT_DATA_PATH: &str = r"tests/data/utf8.txt";
+
+#[test]
+fn data_input_from_file() {
+    common::data_input_from_file(
+        INPUT_DATA_PATH,
+        |text, pattern, answer, answer_not_full, answer_not_full_rev| {
+            for sample in ["", text] {
+                let bm = BMByte::tuned(pattern, sample).unwrap();
+
+                assert_eq!(answer, bm.find_full_all_in(text));
+                assert_eq!(
+                    answer.iter().rev().copied().collect::<Vec<usize>>(),
+                    bm.rfind_full_all_in(text)
+                );
+                assert_eq!(answer_not_full, bm.find_all_in(text));
+                assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+            }
+        },
+    );
+}
+
+#[test]
+fn reasons() {
+    let bm = BMByte::tuned("x", "abcx").unwrap();
+    let tuning = bm.tuning().unwrap();
+
+    assert_eq!(BMByteTuningReason::SingleByte, tuning.reason);
+    assert_eq!(Algorithm::TunedBoyerMoore, bm.algorithm());
+    assert_eq!(1, tuning.last_byte_occurrences);
+
+    let bm = BMByte::tuned("abcabcabc", "").unwrap();
+    let tuning = bm.tuning().unwrap();
+
+    assert_eq!(BMByteTuningReason::Periodic, tuning.reason);
+    assert_eq!(3, tuning.period);
+    assert!(tuning.good_suffix);
+
+    let bm = BMByte::tuned("ACGTTGCAACGTAGCTAGCTAAGT", "").unwrap();
+    let tuning = bm.tuning().unwrap();
+
+    assert_eq!(BMByteTuningReason::SmallAlphabet, tuning.reason);
+    assert_eq!(4, tuning.alphabet_size);
+    assert!(tuning.read_counts.is_empty());
+
+    // the algorithm is the one which `BMByte::from` would choose
+    for pattern in ["example", "examples and more"] {
+        let bm = BMByte::tuned(pattern, "").unwrap();
+        let tuning = bm.tuning().unwrap();
+
+        assert_eq!(BMByteTuningReason::Default, tuning.reason);
+        assert_eq!(BMByte::from(pattern).unwrap().algorithm(), bm.algorithm());
+        assert_eq!(bm.algorithm(), tuning.algorithm);
+    }
+
+    assert_eq!(Algorithm::ShiftOr, BMByte::tuned("example", "").unwrap().algorithm());
+
+    assert!(BMByte::from("example").unwrap().tuning().is_none());
+}
+
+#[test]
+fn measured() {
+    let text = fs::read_to_string(r"benches/data/vgilante.txt").unwrap();
+
+    let bm = BMByte::tuned("screen", text.as_str()).unwrap();
+    let tuning = bm.tuning().unwrap();
+
+    assert_eq!(BMByteTuningReason::FewestReads, tuning.reason);
+    assert_eq!(8, tuning.read_counts.len());
+
+    // Shift-Or reads every byte
+    assert!(tuning
+        .read_counts
+        .iter()
+        .any(|&(algorithm, _, reads)| algorithm == Algorithm::ShiftOr && reads == text.len()));
+    assert!(tuning.read_counts.iter().any(|&(algorithm, ..)| algorithm == Algorithm::Bndm));
+
+    let &(algorithm, good_suffix, reads) =
+        tuning.read_counts.iter().min_by_key(|(_, _, reads)| *reads).unwrap();
+
+    assert_eq!(algorithm, tuning.algorithm);
+    assert_eq!(good_suffix, tuning.good_suffix);
+    assert!(reads < text.len());
+
+    assert_eq!(
+        BMByte::from("screen").unwrap().find_all_in(text.as_str()),
+        bm.find_all_in(text.as_str())
+    );
+
+    // the bit-parallel algorithms are not compared for patterns longer than 64 bytes
+    let bm = BMByte::tuned(&text.as_bytes()[..70], text.as_str()).unwrap();
+    let tuning = bm.tuning().unwrap();
+
+    assert_eq!(BMByteTuningReason::FewestReads, tuning.reason);
+    assert_eq!(6, tuning.read_counts.len());
+}
@@ -18,6 +18,102 @@ fn data_input_from_file() {
             );
             assert_eq!(answer_not_full, bm.find_all_in(text));
             assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+
+            let bm = BMByte::from_with_rev(pattern, false).unwrap();
+
+            assert_eq!(
+                answer.iter().rev().copied().collect::<Vec<usize>>(),
+                bm.rfind_full_all_in(text)
+            );
+            assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+
+            let bm_forward = BMByteForward::from(pattern).unwrap();
+
+            assert_eq!(answer, bm_forward.find_full_all_in(text));
+            assert_eq!(answer_not_full, bm_forward.find_all_in(text));
+
+            let bm_reverse = BMByteReverse::from(pattern).unwrap();
+
+            assert_eq!(
+                answer.iter().rev().copied().collect::<Vec<usize>>(),
+                bm_reverse.rfind_full_all_in(text)
+            );
+            assert_eq!(answer_not_full_rev, bm_reverse.rfind_all_in(text));
+
+            for rev in [true, false] {
+                let bm = BMByte::builder(pattern).rev(rev).good_suffix(true).build().unwrap();
+
+                assert_eq!(answer, bm.find_full_all_in(text));
+                assert_eq!(
+                    answer.iter().rev().copied().collect::<Vec<usize>>(),
+                    bm.rfind_full_all_in(text)
+                );
+                assert_eq!(answer_not_full, bm.find_all_in(text));
+                assert_eq!(answer_not_full_rev, bm.rfind_all_in(text));
+            }
         },
     );
 }
+
+#[test]
+fn good_suffix_repetitive() {
+    common::check_against_naive(
+        common::repetitive_cases(),
+        &common::Search::ALL,
+        |pattern| BMByte::builder(pattern).good_suffix(true).build().unwrap(),
+        common::search_bmbyte,
+    );
+}
+
+#[test]
+fn period_repetitive() {
+    // the short patterns would be searched by Shift-Or without setting the algorithm
+    common::check_against_naive(
+        common::repetitive_cases(),
+        &common::Search::ALL,
+        |pattern| BMByte::builder(pattern).algorithm(Algorithm::MagicLen).build().unwrap(),
+        common::search_bmbyte,
+    );
+
+    common::check_against_naive(
+        common::repetitive_cases(),
+        &[common::Search::FindFull, common::Search::Find],
+        |pattern| BMByteForward::from(pattern).unwrap(),
+        |bm, text, kind, limit| match kind {
+            common::Search::FindFull => bm.find_full_in(text, limit),
+            _ => bm.find_in(text, limit),
+        },
+    );
+
+    common::check_against_naive(
+        common::repetitive_cases(),
+        &[common::Search::RFindFull, common::Search::RFind],
+        |pattern| BMByteReverse::from(pattern).unwrap(),
+        |bm, text, kind, limit| match kind {
+            common::Search::RFindFull => bm.rfind_full_in(text, limit),
+            _ => bm.rfind_in(text, limit),
+        },
+    );
+}
+
+#[test]
+fn period_large() {
+    let text = "abc".repeat(300_000);
+    let pattern = "abc".repeat(1000) + "ab";
+
+    let answer = common::naive_find_full(text.as_bytes(), pattern.as_bytes());
+    let answer_rev = answer.iter().rev().copied().collect::<Vec<usize>>();
+
+    assert_eq!(300_000 - 1000, answer.len());
+
+    for good_suffix in [false, true] {
+        let bm = BMByte::builder(pattern.as_str())
+            .algorithm(Algorithm::MagicLen)
+            .good_suffix(good_suffix)
+            .build()
+            .unwrap();
+
+        assert_eq!(answer, bm.find_full_all_in(text.as_str()));
+        assert_eq!(answer_rev, bm.rfind_full_all_in(text.as_str()));
+    }
+}